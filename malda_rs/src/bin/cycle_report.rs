@@ -0,0 +1,164 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Executes the get-proof-data guest for every supported chain and lane and reports cycles.
+//!
+//! Example:
+//! ```bash
+//! cargo run --release --bin cycle_report -- --sepolia --json cycles.json
+//! ```
+//!
+//! Each case runs `estimate_proof_cycles` for a single user, so the numbers track the fixed
+//! per-chain validation cost of the guest. Cases that fail are reported with their error and
+//! don't stop the run. Reads the same environment variables as `prove_user`.
+
+use alloy::primitives::{address, Address};
+use anyhow::Result;
+use clap::Parser;
+use malda_rs::constants::*;
+use malda_rs::viewcalls::estimate_proof_cycles;
+use serde::Serialize;
+use tracing_subscriber::EnvFilter;
+
+/// Queried user; the guest cost doesn't depend on the user's balances.
+const USER: Address = address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3");
+
+#[derive(Parser, Debug)]
+#[command(about = "Report guest cycles per chain and lane")]
+struct Args {
+    /// Run against the Sepolia deployments instead of mainnet
+    #[arg(long)]
+    sepolia: bool,
+    /// Also write the report as JSON to this path
+    #[arg(long)]
+    json: Option<std::path::PathBuf>,
+}
+
+/// Cycle counts for one chain/lane combination.
+#[derive(Debug, Serialize)]
+struct CaseReport {
+    chain_id: u64,
+    chain: &'static str,
+    l1_inclusion: bool,
+    segments: Option<usize>,
+    user_cycles: Option<u64>,
+    total_cycles: Option<u64>,
+    seconds: f64,
+    error: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let args = Args::parse();
+
+    let (market, cases) = if args.sepolia {
+        (
+            WETH_MARKET_SEPOLIA,
+            [
+                (LINEA_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID),
+                (OPTIMISM_SEPOLIA_CHAIN_ID, LINEA_SEPOLIA_CHAIN_ID),
+                (BASE_SEPOLIA_CHAIN_ID, LINEA_SEPOLIA_CHAIN_ID),
+                (ETHEREUM_SEPOLIA_CHAIN_ID, LINEA_SEPOLIA_CHAIN_ID),
+            ],
+        )
+    } else {
+        (
+            WETH_MARKET,
+            [
+                (LINEA_CHAIN_ID, BASE_CHAIN_ID),
+                (OPTIMISM_CHAIN_ID, LINEA_CHAIN_ID),
+                (BASE_CHAIN_ID, LINEA_CHAIN_ID),
+                (ETHEREUM_CHAIN_ID, LINEA_CHAIN_ID),
+            ],
+        )
+    };
+
+    let mut reports = Vec::new();
+    for (chain_id, target_chain_id) in cases {
+        for l1_inclusion in [false, true] {
+            let start = std::time::Instant::now();
+            let result = estimate_proof_cycles(
+                vec![vec![USER]],
+                vec![vec![market]],
+                vec![vec![target_chain_id]],
+                vec![chain_id],
+                l1_inclusion,
+            )
+            .await;
+            let seconds = start.elapsed().as_secs_f64();
+
+            let report = match result {
+                Ok(stats) => CaseReport {
+                    chain_id,
+                    chain: chain_name(chain_id),
+                    l1_inclusion,
+                    segments: Some(stats.segments),
+                    user_cycles: Some(stats.user_cycles),
+                    total_cycles: Some(stats.total_cycles),
+                    seconds,
+                    error: None,
+                },
+                Err(err) => CaseReport {
+                    chain_id,
+                    chain: chain_name(chain_id),
+                    l1_inclusion,
+                    segments: None,
+                    user_cycles: None,
+                    total_cycles: None,
+                    seconds,
+                    error: Some(format!("{:#}", err)),
+                },
+            };
+            reports.push(report);
+        }
+    }
+
+    print_table(&reports);
+
+    if let Some(path) = args.json {
+        std::fs::write(&path, serde_json::to_string_pretty(&reports)?)?;
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Prints the reports as a fixed-width table.
+///
+/// `total` is the sum of padded segment sizes, so `total - user` is the paging and padding
+/// overhead.
+fn print_table(reports: &[CaseReport]) {
+    let cell = |value: Option<u64>| value.map_or("-".to_string(), |v| v.to_string());
+
+    println!(
+        "{:<18} {:<6} {:>8} {:>14} {:>14} {:>8}",
+        "chain", "l1", "segments", "user", "total", "seconds"
+    );
+    for report in reports {
+        println!(
+            "{:<18} {:<6} {:>8} {:>14} {:>14} {:>8.1}",
+            report.chain,
+            report.l1_inclusion,
+            cell(report.segments.map(|s| s as u64)),
+            cell(report.user_cycles),
+            cell(report.total_cycles),
+            report.seconds
+        );
+        if let Some(error) = &report.error {
+            println!("    error: {}", error);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Generates a proof for a single user/market pair and prints the journal and seal.
+//!
+//! Example:
+//! ```bash
+//! cargo run --release --bin prove_user -- \
+//!     --user 0x... --market 0x... --chain-id 59144 --target-chain-id 8453 --l1-inclusion --bonsai
+//! ```
+//!
+//! Environment variables (read from the process environment or a `.env` file):
+//! - `RPC_URL_{ETHEREUM,OPTIMISM,BASE,LINEA}` and their `_SEPOLIA` variants - RPC endpoints for
+//!   the queried chain and, when `--l1-inclusion` is set, Ethereum
+//! - `SEQUENCER_REQUEST_{OPTIMISM,BASE}` and their `_SEPOLIA` variants - sequencer commitment
+//!   endpoints, needed for OpStack chains and for Ethereum/Linea L1 anchoring
+//! - `BONSAI_API_KEY`, `BONSAI_API_URL`, `IMAGE_ID_BONSAI` - required with `--bonsai`
+
+use alloy::primitives::Address;
+use anyhow::Result;
+use clap::Parser;
+use malda_rs::viewcalls::{get_proof_data_prove, get_proof_data_prove_sdk};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+#[command(about = "Generate a proof data proof for a single user")]
+struct Args {
+    /// User address to query
+    #[arg(long)]
+    user: Address,
+    /// Market contract address to query
+    #[arg(long)]
+    market: Address,
+    /// Chain ID the market is deployed on
+    #[arg(long)]
+    chain_id: u64,
+    /// Destination chain ID passed to getProofData
+    #[arg(long)]
+    target_chain_id: u64,
+    /// Anchor the proof to L1 (slow lane) instead of the sequencer
+    #[arg(long)]
+    l1_inclusion: bool,
+    /// Prove remotely with Bonsai
+    #[arg(long, conflicts_with = "local")]
+    bonsai: bool,
+    /// Prove locally with the default prover
+    #[arg(long)]
+    local: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let args = Args::parse();
+    if !args.bonsai && !args.local {
+        anyhow::bail!("one of --bonsai or --local must be set");
+    }
+
+    let users = vec![vec![args.user]];
+    let markets = vec![vec![args.market]];
+    let target_chain_ids = vec![vec![args.target_chain_id]];
+    let chain_ids = vec![args.chain_id];
+
+    let (journal, seal) = if args.bonsai {
+        let proof_info = get_proof_data_prove_sdk(
+            users,
+            markets,
+            target_chain_ids,
+            chain_ids,
+            args.l1_inclusion,
+        )
+        .await?;
+        (proof_info.journal_bytes, proof_info.seal_bytes)
+    } else {
+        let proof_info = get_proof_data_prove(
+            users,
+            markets,
+            target_chain_ids,
+            chain_ids,
+            args.l1_inclusion,
+        )
+        .await?;
+        let seal = risc0_ethereum_contracts::encode_seal(&proof_info.receipt)?;
+        (proof_info.receipt.journal.bytes, seal)
+    };
+
+    println!("journal: 0x{}", hex::encode(journal));
+    println!("seal: 0x{}", hex::encode(seal));
+
+    Ok(())
+}
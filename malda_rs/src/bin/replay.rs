@@ -0,0 +1,86 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Verifies a past proof from a log line and prints the journal entries it attests to.
+//!
+//! The line may be a JSON object with `journal` and `seal` hex fields (top level or under
+//! `fields`, as emitted by the JSON tracing formatter) or plain text containing
+//! `journal: 0x...` and `seal: 0x...`, as printed by `prove_user`.
+//!
+//! Example:
+//! ```bash
+//! cargo run --release --bin replay -- --line '{"journal":"0x...","seal":"0x..."}'
+//! cargo run --release --bin prove_user -- ... | cargo run --release --bin replay
+//! ```
+
+use std::io::Read;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use malda_rs::seal::verify_proof_data_seal;
+
+#[derive(Parser, Debug)]
+#[command(about = "Verify a logged proof data seal and print its journal")]
+struct Args {
+    /// Log line or JSONL entry to replay; read from stdin when omitted
+    #[arg(long)]
+    line: Option<String>,
+}
+
+/// Extracts a hex field from a JSON object or a `key: 0x...` style log line.
+fn extract_hex(text: &str, key: &str) -> Option<Vec<u8>> {
+    if let Ok(serde_json::Value::Object(object)) = serde_json::from_str(text) {
+        let value = object
+            .get(key)
+            .or_else(|| object.get("fields").and_then(|fields| fields.get(key)))
+            .and_then(|value| value.as_str())?;
+        return hex::decode(value.trim_start_matches("0x")).ok();
+    }
+
+    text.match_indices(key).find_map(|(index, _)| {
+        let rest = text[index + key.len()..].trim_start_matches(['"', '\'', ':', '=', ' ']);
+        let digits = rest.strip_prefix("0x")?;
+        let end = digits
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(digits.len());
+        hex::decode(&digits[..end]).ok()
+    })
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let text = match args.line {
+        Some(line) => line,
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .context("failed to read stdin")?;
+            input
+        }
+    };
+
+    let Some(journal) = extract_hex(&text, "journal") else {
+        bail!("no journal hex found in input");
+    };
+    let Some(seal) = extract_hex(&text, "seal") else {
+        bail!("no seal hex found in input");
+    };
+
+    let entries = verify_proof_data_seal(&journal, &seal)?;
+    println!("seal verified, {} journal entries", entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        println!("[{}] {:?}", i, entry);
+    }
+
+    Ok(())
+}
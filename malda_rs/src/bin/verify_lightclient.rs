@@ -0,0 +1,85 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Syncs the Ethereum light client from a checkpoint and prints the verified beacon root.
+//!
+//! Runs the same sync committee verification the light client guest performs, without
+//! proving, to check that a checkpoint is still syncable.
+//!
+//! Example:
+//! ```bash
+//! cargo run --release --bin verify_lightclient -- \
+//!     --beacon-rpc https://... --checkpoint 0x...
+//! ```
+
+use alloy::primitives::B256;
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use consensus::rpc::{nimbus_rpc::NimbusRpc, ConsensusRpc};
+use consensus_core::{
+    calc_sync_period,
+    types::{Bootstrap, OptimisticUpdate, Update},
+};
+use malda_rs::validators_ethereum_light_client::{L1ChainBuilder, OldB256};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+#[command(about = "Sync the Ethereum light client from a checkpoint")]
+struct Args {
+    /// Beacon node RPC endpoint
+    #[arg(long)]
+    beacon_rpc: String,
+    /// Trusted beacon block root to bootstrap from
+    #[arg(long)]
+    checkpoint: B256,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let args = Args::parse();
+    let beacon_rpc = NimbusRpc::new(&args.beacon_rpc);
+    let checkpoint = OldB256::from(args.checkpoint.0);
+
+    let bootstrap: Bootstrap = beacon_rpc
+        .get_bootstrap(checkpoint)
+        .await
+        .map_err(|err| anyhow!("failed to fetch bootstrap: {:#}", err))?;
+    let current_period = calc_sync_period(bootstrap.header.beacon.slot);
+    // Same number of sync committee periods as fetched for proving
+    let updates: Vec<Update> = beacon_rpc
+        .get_updates(current_period, 10)
+        .await
+        .map_err(|err| anyhow!("failed to fetch updates: {:#}", err))?;
+    let finality_update: OptimisticUpdate = beacon_rpc
+        .get_optimistic_update()
+        .await
+        .map_err(|err| anyhow!("failed to fetch optimistic update: {:#}", err))?;
+    let slot_last_update = finality_update.attested_header.beacon.slot;
+    let fetched_updates = updates.len();
+
+    let mut l1_chain_builder = L1ChainBuilder::new();
+    let verified_root = l1_chain_builder
+        .build_beacon_chain(bootstrap, checkpoint, updates, finality_update)
+        .map_err(|err| anyhow!("light client sync failed: {:#}", err))?;
+    let new_checkpoint = l1_chain_builder.last_checkpoint.unwrap_or(args.checkpoint);
+
+    println!("updates fetched: {}", fetched_updates);
+    println!("verified root: {}", verified_root);
+    println!("new checkpoint: {}", new_checkpoint);
+    println!("last update slot: {}", slot_last_update);
+
+    Ok(())
+}
@@ -26,141 +26,424 @@ mod constants;
 
 pub use constants::*;
 
-/// RPC endpoint URLs for supported networks
+use crate::secrets::{required_secret, secret};
+use crate::types::{Chain, Network};
+use alloy_primitives::{address, Address, U256};
+use std::time::Duration;
+
+/// RPC endpoint URLs for supported networks, read through the secret source as they embed
+/// provider API keys, see `crate::secrets`
 pub fn rpc_url_linea() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_LINEA")
-            .expect("RPC_URL_LINEA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_LINEA")
 }
 
 pub fn rpc_url_scroll() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_SCROLL")
-            .expect("RPC_URL_SCROLL must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_SCROLL")
 }
 
 pub fn rpc_url_ethereum() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_ETHEREUM")
-            .expect("RPC_URL_ETHEREUM must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_ETHEREUM")
 }
 
 pub fn rpc_url_base() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_BASE")
-            .expect("RPC_URL_BASE must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_BASE")
 }
 
 pub fn rpc_url_optimism() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_OPTIMISM")
-            .expect("RPC_URL_OPTIMISM must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_OPTIMISM")
 }
 
 pub fn rpc_url_arbitrum() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_ARBITRUM")
-            .expect("RPC_URL_ARBITRUM must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_ARBITRUM")
 }
 
 /// Sepolia testnet RPCs
 pub fn rpc_url_linea_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_LINEA_SEPOLIA")
-            .expect("RPC_URL_LINEA_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_LINEA_SEPOLIA")
 }
 
 pub fn rpc_url_scroll_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_SCROLL_SEPOLIA")
-            .expect("RPC_URL_SCROLL_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_SCROLL_SEPOLIA")
 }
 
 pub fn rpc_url_ethereum_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_ETHEREUM_SEPOLIA")
-            .expect("RPC_URL_ETHEREUM_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_ETHEREUM_SEPOLIA")
 }
 
 pub fn rpc_url_base_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_BASE_SEPOLIA")
-            .expect("RPC_URL_BASE_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_BASE_SEPOLIA")
 }
 
 pub fn rpc_url_optimism_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_OPTIMISM_SEPOLIA")
-            .expect("RPC_URL_OPTIMISM_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_OPTIMISM_SEPOLIA")
 }
 
 pub fn rpc_url_arbitrum_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_ARBITRUM_SEPOLIA")
-            .expect("RPC_URL_ARBITRUM_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_ARBITRUM_SEPOLIA")
 }
 
 pub fn rpc_url_beacon() -> &'static str {
-    Box::leak(
-        dotenvy::var("RPC_URL_BEACON")
-            .expect("RPC_URL_BEACON must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("RPC_URL_BEACON")
+}
+
+/// Beacon RPC URLs, `RPC_URL_BEACON` followed by the comma separated
+/// `RPC_URL_BEACON_FALLBACKS`, if set.
+///
+/// The light client data is fetched from the first URL that serves it.
+pub fn rpc_urls_beacon() -> Vec<&'static str> {
+    let mut urls = vec![rpc_url_beacon()];
+    let fallbacks = secret("RPC_URL_BEACON_FALLBACKS")
+        .unwrap_or_else(|e| panic!("Failed to read RPC_URL_BEACON_FALLBACKS: {:#}", e));
+    if let Some(fallbacks) = fallbacks {
+        urls.extend(
+            fallbacks
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(|url| &*Box::leak(url.to_string().into_boxed_str())),
+        );
+    }
+    urls
 }
 
 /// Sequencer request URLs for Layer 2 networks
 pub fn sequencer_request_optimism() -> &'static str {
-    Box::leak(
-        dotenvy::var("SEQUENCER_REQUEST_OPTIMISM")
-            .expect("SEQUENCER_REQUEST_OPTIMISM must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("SEQUENCER_REQUEST_OPTIMISM")
 }
 
 pub fn sequencer_request_base() -> &'static str {
-    Box::leak(
-        dotenvy::var("SEQUENCER_REQUEST_BASE")
-            .expect("SEQUENCER_REQUEST_BASE must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("SEQUENCER_REQUEST_BASE")
 }
 
 pub fn sequencer_request_optimism_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("SEQUENCER_REQUEST_OPTIMISM_SEPOLIA")
-            .expect("SEQUENCER_REQUEST_OPTIMISM_SEPOLIA must be set in environment")
-            .into_boxed_str(),
-    )
+    required_secret("SEQUENCER_REQUEST_OPTIMISM_SEPOLIA")
 }
 
 pub fn sequencer_request_base_sepolia() -> &'static str {
-    Box::leak(
-        dotenvy::var("SEQUENCER_REQUEST_BASE_SEPOLIA")
-            .expect("SEQUENCER_REQUEST_BASE_SEPOLIA must be set in environment")
-            .into_boxed_str(),
+    required_secret("SEQUENCER_REQUEST_BASE_SEPOLIA")
+}
+
+/// Address of the WETH market, shared across mainnet chains.
+pub const WETH_MARKET: Address = address!("C7Bc6bD45Eb84D594f51cED3c5497E6812C7732f");
+/// Address of the WETH market, shared across sepolia chains.
+pub const WETH_MARKET_SEPOLIA: Address = address!("B84644c24B4D0823A0770ED698f7C20B88Bcf824");
+
+/// Human readable name for a chain ID, or `"Unknown"` if the chain is not supported.
+pub fn chain_name(chain_id: u64) -> &'static str {
+    match chain_id {
+        ETHEREUM_CHAIN_ID => "Ethereum",
+        OPTIMISM_CHAIN_ID => "Optimism",
+        BASE_CHAIN_ID => "Base",
+        LINEA_CHAIN_ID => "Linea",
+        SCROLL_CHAIN_ID => "Scroll",
+        ETHEREUM_SEPOLIA_CHAIN_ID => "Ethereum Sepolia",
+        OPTIMISM_SEPOLIA_CHAIN_ID => "Optimism Sepolia",
+        BASE_SEPOLIA_CHAIN_ID => "Base Sepolia",
+        LINEA_SEPOLIA_CHAIN_ID => "Linea Sepolia",
+        SCROLL_SEPOLIA_CHAIN_ID => "Scroll Sepolia",
+        _ => "Unknown",
+    }
+}
+
+/// Proving support of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainSupport {
+    /// Chain ID.
+    pub chain_id: u64,
+    /// Human readable chain name.
+    pub name: &'static str,
+    /// Network the chain belongs to.
+    pub network: Network,
+    /// Whether the get proof data guest accepts queries on the chain.
+    pub fast_lane: bool,
+    /// Whether the chain's state can additionally be proven included in Ethereum.
+    pub slow_lane: bool,
+    /// Whether the Ethereum light client guest can prove the chain.
+    pub light_client: bool,
+    /// Reorg protection depth of the chain.
+    pub reorg_depth: u64,
+}
+
+/// Returns the proving support of a chain, or `None` if the chain is unknown.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to look up
+pub fn chain_support(chain_id: u64) -> Option<ChainSupport> {
+    let chain = Chain::from_id(chain_id)?;
+    let fast_lane = is_supported_chain(chain_id);
+    Some(ChainSupport {
+        chain_id,
+        name: chain_name(chain_id),
+        network: chain.network(),
+        fast_lane,
+        slow_lane: fast_lane && (chain.is_opstack() || chain.is_linea()),
+        light_client: chain == Chain::Ethereum,
+        reorg_depth: chain.reorg_depth(),
+    })
+}
+
+/// Returns the proving support of every chain known to the crate.
+pub fn supported_chains() -> Vec<ChainSupport> {
+    [
+        ETHEREUM_CHAIN_ID,
+        OPTIMISM_CHAIN_ID,
+        BASE_CHAIN_ID,
+        LINEA_CHAIN_ID,
+        SCROLL_CHAIN_ID,
+        ETHEREUM_SEPOLIA_CHAIN_ID,
+        OPTIMISM_SEPOLIA_CHAIN_ID,
+        BASE_SEPOLIA_CHAIN_ID,
+        LINEA_SEPOLIA_CHAIN_ID,
+        SCROLL_SEPOLIA_CHAIN_ID,
+    ]
+    .into_iter()
+    .filter_map(chain_support)
+    .collect()
+}
+
+/// A market known to the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketInfo {
+    /// Human readable market name.
+    pub name: &'static str,
+    /// Market contract address. Markets are deployed at the same address on every chain of
+    /// a network.
+    pub address: Address,
+    /// Decimals of the market's underlying asset.
+    pub decimals: u8,
+}
+
+impl MarketInfo {
+    /// Formats a raw amount of the underlying asset, e.g. `"4.088287 USDC"`.
+    pub fn format_amount(&self, amount: U256) -> String {
+        format!("{} {}", format_units(amount, self.decimals), self.name)
+    }
+}
+
+/// Formats a raw token amount as a decimal string, trimming trailing zeros.
+///
+/// # Arguments
+/// * `amount` - Raw amount in the token's smallest unit
+/// * `decimals` - Decimals of the token
+///
+/// # Returns
+/// * `String` - The amount with a decimal point, e.g. `"4.088287"` for `4088287` and 6 decimals
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    let unit = U256::from(10).pow(U256::from(decimals));
+    let whole = amount / unit;
+    let fraction = amount % unit;
+    if fraction.is_zero() {
+        return whole.to_string();
+    }
+    let fraction = format!(
+        "{:0>width$}",
+        fraction.to_string(),
+        width = decimals as usize
+    );
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+/// Lookup table from market address to market metadata.
+///
+/// `MarketRegistry::default()` contains the markets known to this crate; integrators can
+/// `register` additional ones.
+#[derive(Debug, Clone)]
+pub struct MarketRegistry {
+    markets: Vec<MarketInfo>,
+}
+
+impl MarketRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            markets: Vec::new(),
+        }
+    }
+
+    /// Adds a market, replacing any existing entry with the same address.
+    pub fn register(&mut self, name: &'static str, address: Address, decimals: u8) {
+        self.markets.retain(|market| market.address != address);
+        self.markets.push(MarketInfo {
+            name,
+            address,
+            decimals,
+        });
+    }
+
+    /// Returns the market registered at `address`.
+    pub fn get(&self, address: Address) -> Option<&MarketInfo> {
+        self.markets.iter().find(|market| market.address == address)
+    }
+
+    /// Human readable name for a market, or `"Unknown"` if it is not registered.
+    pub fn market_name(&self, address: Address) -> &'static str {
+        self.get(address).map_or("Unknown", |market| market.name)
+    }
+
+    /// Formats an amount of a market's underlying asset for logging.
+    ///
+    /// Registered markets log the human readable amount next to the raw value, e.g.
+    /// `"4.088287 USDC (4088287)"`; unknown markets log the raw value only.
+    pub fn format_amount(&self, address: Address, amount: U256) -> String {
+        match self.get(address) {
+            Some(market) => format!("{} ({})", market.format_amount(amount), amount),
+            None => amount.to_string(),
+        }
+    }
+
+    /// All registered markets.
+    pub fn markets(&self) -> &[MarketInfo] {
+        &self.markets
+    }
+}
+
+impl Default for MarketRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register("WETH", WETH_MARKET, 18);
+        registry.register("WETH", WETH_MARKET_SEPOLIA, 18);
+        registry
+    }
+}
+
+/// Default deadline for building a single chain's ZKVM input.
+pub const DEFAULT_PROOF_DATA_INPUT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Deadline for building a single chain's ZKVM input.
+///
+/// Read from `PROOF_DATA_INPUT_TIMEOUT_SECS`, falling back to
+/// `DEFAULT_PROOF_DATA_INPUT_TIMEOUT`.
+pub fn proof_data_input_timeout() -> Duration {
+    dotenvy::var("PROOF_DATA_INPUT_TIMEOUT_SECS")
+        .ok()
+        .map(|secs| {
+            Duration::from_secs(
+                secs.parse()
+                    .expect("PROOF_DATA_INPUT_TIMEOUT_SECS must be a number of seconds"),
+            )
+        })
+        .unwrap_or(DEFAULT_PROOF_DATA_INPUT_TIMEOUT)
+}
+
+/// Games checked for a mature finalized dispute game when building the OpStack L1 inclusion
+/// environment fails.
+pub const DISPUTE_GAME_MATURITY_SEARCH_DEPTH: u64 = 50;
+
+/// Maximum attempts to find Optimism and Base heads with the same L1 origin.
+pub const DUAL_OPSTACK_L1_ATTESTATION_MAX_ATTEMPTS: u32 = 10;
+
+/// Delay between attempts to find Optimism and Base heads with the same L1 origin.
+pub const DUAL_OPSTACK_L1_ATTESTATION_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Maximum attempts at building and preflighting the view call environment of a chain.
+pub const PREFLIGHT_MAX_ATTEMPTS: u32 = 4;
+
+/// Delay before the first preflight retry, doubled on every further attempt.
+pub const PREFLIGHT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Whether L1 inclusion is attested through both Optimism and Base.
+///
+/// Enabled by setting `DUAL_OPSTACK_L1_ATTESTATION` to `true` or `1`.
+pub fn dual_opstack_l1_attestation() -> bool {
+    matches!(
+        dotenvy::var("DUAL_OPSTACK_L1_ATTESTATION").as_deref(),
+        Ok("true") | Ok("1")
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_chains() {
+        let mainnet = [
+            (
+                ETHEREUM_CHAIN_ID,
+                "Ethereum",
+                REORG_PROTECTION_DEPTH_ETHEREUM,
+            ),
+            (
+                OPTIMISM_CHAIN_ID,
+                "Optimism",
+                REORG_PROTECTION_DEPTH_OPTIMISM,
+            ),
+            (BASE_CHAIN_ID, "Base", REORG_PROTECTION_DEPTH_BASE),
+            (LINEA_CHAIN_ID, "Linea", REORG_PROTECTION_DEPTH_LINEA),
+            (SCROLL_CHAIN_ID, "Scroll", REORG_PROTECTION_DEPTH_SCROLL),
+        ];
+        let sepolia = [
+            (
+                ETHEREUM_SEPOLIA_CHAIN_ID,
+                "Ethereum Sepolia",
+                REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
+            ),
+            (
+                OPTIMISM_SEPOLIA_CHAIN_ID,
+                "Optimism Sepolia",
+                REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
+            ),
+            (
+                BASE_SEPOLIA_CHAIN_ID,
+                "Base Sepolia",
+                REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
+            ),
+            (
+                LINEA_SEPOLIA_CHAIN_ID,
+                "Linea Sepolia",
+                REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
+            ),
+            (
+                SCROLL_SEPOLIA_CHAIN_ID,
+                "Scroll Sepolia",
+                REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
+            ),
+        ];
+        let fast_lane = [ETHEREUM_CHAIN_ID, BASE_CHAIN_ID, LINEA_CHAIN_ID];
+        let slow_lane = [BASE_CHAIN_ID, LINEA_CHAIN_ID];
+
+        let chains = supported_chains();
+        assert_eq!(chains.len(), mainnet.len() + sepolia.len());
+        for (network, known) in [(Network::Mainnet, mainnet), (Network::Sepolia, sepolia)] {
+            for (chain_id, name, reorg_depth) in known {
+                let expected = ChainSupport {
+                    chain_id,
+                    name,
+                    network,
+                    fast_lane: fast_lane.contains(&chain_id),
+                    slow_lane: slow_lane.contains(&chain_id),
+                    light_client: chain_id == ETHEREUM_CHAIN_ID,
+                    reorg_depth,
+                };
+                assert_eq!(chain_support(chain_id), Some(expected));
+                assert!(chains.contains(&expected), "{} is not listed", name);
+                assert_eq!(chain_name(chain_id), name);
+                assert_eq!(is_supported_chain(chain_id), expected.fast_lane);
+            }
+        }
+
+        assert_eq!(chain_support(42), None);
+        assert_eq!(chain_name(42), "Unknown");
+    }
+
+    #[test]
+    fn test_market_registry_formats_amounts() {
+        assert_eq!(format_units(U256::from(4_088_287u64), 6), "4.088287");
+        assert_eq!(format_units(U256::from(5_000_000u64), 6), "5");
+        assert_eq!(format_units(U256::from(1_500u64), 6), "0.0015");
+        assert_eq!(format_units(U256::from(42u64), 0), "42");
+
+        let usdc_market = address!("Ad7f33984bed10518012013D4aB0458D37FEE6F3");
+        let mut registry = MarketRegistry::default();
+        registry.register("USDC", usdc_market, 6);
+        assert_eq!(
+            registry.format_amount(usdc_market, U256::from(4_088_287u64)),
+            "4.088287 USDC (4088287)"
+        );
+        assert_eq!(
+            registry.format_amount(WETH_MARKET, U256::from(10u64).pow(U256::from(18))),
+            "1 WETH (1000000000000000000)"
+        );
+        assert_eq!(registry.format_amount(Address::ZERO, U256::from(7u64)), "7");
+    }
+}
@@ -32,4 +32,12 @@ pub mod validators;
 #[path = "../../malda_utils/src/validators_ethereum_light_client.rs"]
 pub mod validators_ethereum_light_client;
 
+#[cfg(test)]
+#[path = "../../malda_utils/src/test_support.rs"]
+pub(crate) mod test_support;
+
 pub mod elfs_ids;
+
+pub mod seal;
+
+pub mod secrets;
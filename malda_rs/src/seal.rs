@@ -0,0 +1,197 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Offline verification of encoded proof data seals.
+//!
+//! A seal as produced by `risc0_ethereum_contracts::encode_seal` together with its journal is
+//! enough to check a proof without the prover, e.g. when replaying a proof taken from logs.
+
+use crate::elfs_ids::GET_PROOF_DATA_ID;
+use crate::types::{ProofDataJournalEntry, ProofLane};
+
+use anyhow::{bail, Context, Error};
+use risc0_ethereum_contracts::{
+    receipt::{decode_seal, Receipt as DecodedReceipt},
+    selector::Selector,
+};
+
+/// Verifies an encoded seal against the get proof data image and decodes its journal.
+///
+/// # Arguments
+/// * `journal` - ABI encoded journal bytes committed by the guest
+/// * `seal` - Seal bytes as submitted on chain, including the selector
+///
+/// # Returns
+/// * `Result<Vec<ProofDataJournalEntry>, Error>` - The journal entries the seal attests to
+///
+/// # Errors
+/// Returns an error if the seal cannot be decoded, decodes to a set inclusion receipt, fails
+/// verification against `GET_PROOF_DATA_ID`, or the journal is malformed.
+pub fn verify_proof_data_seal(
+    journal: &[u8],
+    seal: &[u8],
+) -> Result<Vec<ProofDataJournalEntry>, Error> {
+    let decoded = decode_seal(seal.to_vec().into(), GET_PROOF_DATA_ID, journal.to_vec())
+        .context("failed to decode seal")?;
+    match decoded {
+        DecodedReceipt::Base(receipt) => receipt
+            .verify(GET_PROOF_DATA_ID)
+            .context("seal verification failed")?,
+        _ => bail!("seal decoded to a set inclusion receipt"),
+    }
+
+    ProofDataJournalEntry::decode_journal(journal).map_err(|err| anyhow::anyhow!("{:#}", err))
+}
+
+/// Checks that a journal and seal can be submitted on chain.
+///
+/// This is a cheap structural check meant to run before spending gas; it does not verify the
+/// proof, see `verify_proof_data_seal` for that.
+///
+/// # Arguments
+/// * `journal` - ABI encoded journal bytes
+/// * `seal` - Seal bytes including the verifier selector
+///
+/// # Returns
+/// * `Result<Selector, Error>` - The verifier the seal is routed to
+///
+/// # Errors
+/// Returns an error if the journal or seal is empty, or the seal doesn't start with a
+/// selector known to the verifier router.
+pub fn ensure_submittable(journal: &[u8], seal: &[u8]) -> Result<Selector, Error> {
+    if journal.is_empty() {
+        bail!("journal is empty");
+    }
+    if seal.is_empty() {
+        bail!("seal is empty");
+    }
+    let Some(selector) = seal_selector(seal) else {
+        bail!("seal is shorter than a verifier selector");
+    };
+    Selector::from_bytes(selector)
+        .with_context(|| format!("unknown verifier selector 0x{}", hex::encode(selector)))
+}
+
+/// Returns the verifier selector an encoded seal starts with.
+///
+/// # Arguments
+/// * `seal` - Seal bytes as produced by `encode_seal`
+///
+/// # Returns
+/// * `Option<[u8; 4]>` - The selector, or `None` if the seal is shorter than a selector
+pub fn seal_selector(seal: &[u8]) -> Option<[u8; 4]> {
+    seal.get(..4)?.try_into().ok()
+}
+
+/// Checks that a seal is routed to the verifier a destination chain expects.
+///
+/// After a verifier router upgrade a seal with a stale selector reverts on chain, so callers
+/// can compare against the selector the destination's router accepts before submitting.
+///
+/// # Arguments
+/// * `seal` - Seal bytes as produced by `encode_seal`
+/// * `expected` - Selector the destination chain's verifier expects
+///
+/// # Errors
+/// Returns an error if the seal has no selector or a different one.
+pub fn ensure_seal_selector(seal: &[u8], expected: [u8; 4]) -> Result<(), Error> {
+    let Some(selector) = seal_selector(seal) else {
+        bail!("seal is shorter than a verifier selector");
+    };
+    if selector != expected {
+        bail!(
+            "seal selector 0x{} doesn't match expected 0x{}",
+            hex::encode(selector),
+            hex::encode(expected)
+        );
+    }
+    Ok(())
+}
+
+/// Checks that every journal entry was proven on the requested lane.
+///
+/// The guest commits the `l1Inclusion` flag it validated with, so a proof generated for the
+/// wrong lane is caught before it is submitted.
+///
+/// # Arguments
+/// * `journal` - ABI encoded journal bytes committed by the guest
+/// * `lane` - Lane the proof was requested for. `true` selects the slow lane.
+///
+/// # Returns
+/// * `Result<Vec<ProofDataJournalEntry>, Error>` - The decoded journal entries
+///
+/// # Errors
+/// Returns an error if the journal is malformed or an entry's `l1Inclusion` flag doesn't
+/// match the lane.
+pub fn ensure_journal_lane(
+    journal: &[u8],
+    lane: impl Into<ProofLane>,
+) -> Result<Vec<ProofDataJournalEntry>, Error> {
+    let lane = lane.into();
+    let entries = ProofDataJournalEntry::decode_journal(journal)
+        .map_err(|err| anyhow::anyhow!("{:#}", err))?;
+    if let Some((index, entry)) = entries
+        .iter()
+        .enumerate()
+        .find(|(_, entry)| entry.l1_inclusion != lane.l1_inclusion())
+    {
+        bail!(
+            "journal entry {} for chain {} has l1Inclusion {}, expected {:?} lane",
+            index,
+            entry.chain_id,
+            entry.l1_inclusion,
+            lane
+        );
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{BASE_CHAIN_ID, LINEA_CHAIN_ID, WETH_MARKET};
+    use alloy_primitives::{address, U256};
+    use alloy_sol_types::SolValue;
+
+    #[test]
+    fn test_ensure_journal_lane() {
+        let entry = ProofDataJournalEntry {
+            user: address!("0000000000000000000000000000000000000001"),
+            market: WETH_MARKET,
+            amount_in: U256::from(1),
+            amount_out: U256::ZERO,
+            chain_id: BASE_CHAIN_ID,
+            target_chain_id: LINEA_CHAIN_ID,
+            l1_inclusion: true,
+            reorg_protection_depth: 0,
+        };
+        let journal = vec![entry.encode_packed()].abi_encode();
+
+        assert_eq!(
+            ensure_journal_lane(&journal, ProofLane::Slow).unwrap(),
+            vec![entry]
+        );
+        assert!(ensure_journal_lane(&journal, ProofLane::Fast).is_err());
+        assert!(ensure_journal_lane(&journal[1..], true).is_err());
+    }
+
+    #[test]
+    fn test_reject_unsubmittable_seals() {
+        let journal = [0u8; 64];
+        assert!(ensure_submittable(&[], &[0u8; 260]).is_err());
+        assert!(ensure_submittable(&journal, &[]).is_err());
+        assert!(ensure_submittable(&journal, &[0u8; 3]).is_err());
+        assert!(ensure_submittable(&journal, &[0xffu8; 260]).is_err());
+        assert_eq!(seal_selector(&[1, 2, 3]), None);
+        assert!(ensure_seal_selector(&[1, 2, 3], [1, 2, 3, 4]).is_err());
+    }
+}
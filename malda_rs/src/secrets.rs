@@ -0,0 +1,202 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Sources for RPC URLs and other credentials.
+//!
+//! RPC URLs embed provider API keys, so they are read through a `SecretSource` instead of
+//! straight from the environment. The default `EnvSecrets` reads `NAME` from the environment
+//! or `.env`, or else the file named by `NAME_FILE`, which fits mounted Docker and Kubernetes
+//! secrets. Other stores, e.g. a secret manager, are plugged in with `set_secret_source`.
+
+use anyhow::{Context, Error, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// A store secrets are read from by name.
+pub trait SecretSource: Send + Sync {
+    /// Returns the secret called `name`.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the secret, e.g. `RPC_URL_ETHEREUM`.
+    ///
+    /// # Returns
+    /// * `Result<Option<String>, Error>` - The secret, or `None` if the source doesn't hold it.
+    ///
+    /// # Errors
+    /// Returns an error if the source holds the secret but it can't be read.
+    fn get(&self, name: &str) -> Result<Option<String>, Error>;
+}
+
+/// Reads secrets from the environment or `.env`, falling back to the file named by `NAME_FILE`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSecrets;
+
+impl SecretSource for EnvSecrets {
+    fn get(&self, name: &str) -> Result<Option<String>, Error> {
+        var_or_file(name, |var| dotenvy::var(var).ok())
+    }
+}
+
+/// Looks up `name` with `var`, falling back to the file named by `NAME_FILE`.
+fn var_or_file(name: &str, var: impl Fn(&str) -> Option<String>) -> Result<Option<String>, Error> {
+    if let Some(value) = var(name) {
+        return Ok(Some(value));
+    }
+    match var(&format!("{}_FILE", name)) {
+        Some(path) => read_secret_file(Path::new(&path)).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Reads secrets from a directory holding one file per secret, named like the secret.
+#[derive(Debug, Clone)]
+pub struct FileSecrets {
+    dir: PathBuf,
+}
+
+impl FileSecrets {
+    /// Creates a source reading from `dir`, e.g. `/run/secrets`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl SecretSource for FileSecrets {
+    fn get(&self, name: &str) -> Result<Option<String>, Error> {
+        let path = self.dir.join(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_secret_file(&path).map(Some)
+    }
+}
+
+/// Reads a secret file, trimming the trailing newline most tools write.
+fn read_secret_file(path: &Path) -> Result<String, Error> {
+    let value = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read secret file {}", path.display()))?;
+    Ok(value.trim().to_string())
+}
+
+static SECRET_SOURCE: OnceLock<Box<dyn SecretSource>> = OnceLock::new();
+
+/// Installs the source all secrets are read from.
+///
+/// Must be called before the first secret is read, e.g. at startup; until then `EnvSecrets`
+/// is used.
+///
+/// # Arguments
+/// * `source` - The source to read secrets from.
+///
+/// # Errors
+/// Returns an error if a source was already installed or a secret was already read.
+pub fn set_secret_source(source: impl SecretSource + 'static) -> Result<(), Error> {
+    SECRET_SOURCE
+        .set(Box::new(source))
+        .map_err(|_| anyhow::anyhow!("secret source is already set"))
+}
+
+/// Returns the installed secret source, `EnvSecrets` unless `set_secret_source` was called.
+pub fn secret_source() -> &'static dyn SecretSource {
+    SECRET_SOURCE.get_or_init(|| Box::new(EnvSecrets)).as_ref()
+}
+
+/// Reads a secret from the installed source.
+///
+/// # Arguments
+/// * `name` - Name of the secret.
+///
+/// # Returns
+/// * `Result<Option<String>, Error>` - The secret, or `None` if it isn't set.
+///
+/// # Errors
+/// Returns an error if the secret is set but can't be read.
+pub fn secret(name: &str) -> Result<Option<String>, Error> {
+    secret_source().get(name)
+}
+
+/// Reads a secret that must be set, leaking it for the lifetime of the process.
+///
+/// # Arguments
+/// * `name` - Name of the secret.
+///
+/// # Returns
+/// * `&'static str` - The secret.
+///
+/// # Panics
+/// Panics if the secret is not set or can't be read.
+pub fn required_secret(name: &str) -> &'static str {
+    let value = secret(name)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {:#}", name, e))
+        .unwrap_or_else(|| panic!("{} must be set in environment or secret source", name));
+    Box::leak(value.into_boxed_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn secrets_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("malda-{}-{}", test, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("RPC_URL"), "https://rpc.example/key\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_file_secrets() {
+        let dir = secrets_dir("file-secrets");
+        let files = FileSecrets::new(&dir);
+
+        assert_eq!(
+            files.get("RPC_URL").unwrap().as_deref(),
+            Some("https://rpc.example/key")
+        );
+        assert_eq!(files.get("MISSING").unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_var_or_file() {
+        let dir = secrets_dir("var-or-file");
+        let vars = HashMap::from([
+            (
+                "DIRECT".to_string(),
+                "https://rpc.example/direct".to_string(),
+            ),
+            (
+                "RPC_URL_FILE".to_string(),
+                dir.join("RPC_URL").display().to_string(),
+            ),
+            (
+                "BROKEN_FILE".to_string(),
+                dir.join("MISSING").display().to_string(),
+            ),
+        ]);
+        let var = |name: &str| vars.get(name).cloned();
+
+        assert_eq!(
+            var_or_file("DIRECT", var).unwrap().as_deref(),
+            Some("https://rpc.example/direct")
+        );
+        assert_eq!(
+            var_or_file("RPC_URL", var).unwrap().as_deref(),
+            Some("https://rpc.example/key")
+        );
+        assert_eq!(var_or_file("UNSET", var).unwrap(), None);
+        assert!(var_or_file("BROKEN", var).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -27,6 +27,7 @@
 
 use crate::constants::*;
 use crate::elfs_ids::*;
+use crate::secrets::secret;
 use crate::types::*;
 use crate::types::{Call3, IDisputeGame, IDisputeGameFactory, IL1MessageService, IMulticall3};
 use crate::types::{ExecutionPayload, IL1Block, SequencerCommitment};
@@ -37,15 +38,16 @@ use risc0_steel::{
     ethereum::EthEvmEnv, host::BlockNumberOrTag, serde::RlpHeader, Contract, EvmInput,
 };
 use risc0_zkvm::{
-    default_executor, default_prover, ExecutorEnv, ProveInfo, ProverOpts, SessionInfo,
+    default_executor, default_prover, sha::Digest, ExecutorEnv, ExternalProver, ProveInfo, Prover,
+    ProverOpts, SessionInfo,
 };
 
 use risc0_op_steel::{optimism::OpEvmEnv, DisputeGameIndex};
 
-use alloy::primitives::{Address, U256, U64};
+use alloy::primitives::{Address, U256};
 use alloy_consensus::Header;
 
-use anyhow::{Error, Result};
+use anyhow::{Context, Error, Result};
 use bonsai_sdk;
 use futures::future::join_all;
 use tokio;
@@ -68,19 +70,537 @@ pub struct MaldaSessionStats {
     pub reserved_cycles: u64,
 }
 
+/// Kind of receipt returned by the SDK proving path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReceiptKind {
+    /// STARK wrapped into a Groth16 SNARK, verifiable on-chain.
+    #[default]
+    Groth16,
+    /// Succinct STARK receipt without the SNARK wrap, for aggregation and recursion.
+    Succinct,
+}
+
+/// Prover used for Groth16 proofs of the proof data guest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProvingBackend {
+    /// Remote proving on Bonsai.
+    #[default]
+    Bonsai,
+    /// Proving on the local machine through `r0vm`, for operators running their own proving
+    /// infrastructure. `RISC0_SERVER_PATH` overrides the `r0vm` found on `PATH`.
+    Local,
+}
+
+impl ProvingBackend {
+    /// Reads the backend from `PROVING_BACKEND`, `bonsai` or `local`, defaulting to Bonsai.
+    ///
+    /// # Errors
+    /// Returns an error if `PROVING_BACKEND` holds another value.
+    pub fn from_env() -> Result<Self, Error> {
+        match dotenvy::var("PROVING_BACKEND").as_deref() {
+            Err(_) | Ok("bonsai") => Ok(ProvingBackend::Bonsai),
+            Ok("local") => Ok(ProvingBackend::Local),
+            Ok(other) => anyhow::bail!("PROVING_BACKEND must be bonsai or local, got {}", other),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct MaldaProveInfo {
     pub receipt: Receipt,
+    /// Kind of `receipt`.
+    pub receipt_kind: ReceiptKind,
     pub stats: MaldaSessionStats,
     pub uuid: String,
     pub stark_time: u64,
+    /// Time spent on the SNARK wrap, `0` for succinct receipts.
     pub snark_time: u64,
+    /// Groth16 seal encoded for on-chain verification (selector prefixed). Empty for succinct
+    /// receipts, which have no on-chain verifier.
+    pub seal_bytes: Vec<u8>,
+    /// Journal committed by the guest.
+    pub journal_bytes: Vec<u8>,
+    /// Network of the proven chains. Only verifiers on this network accept the proof.
+    pub network: Network,
+}
+
+impl MaldaProveInfo {
+    /// Checks that the proof can be submitted to a destination chain.
+    ///
+    /// Mainnet and Sepolia use different verifier deployments, so a proof is only accepted on
+    /// chains of the network it was generated for.
+    ///
+    /// # Arguments
+    /// * `chain_id` - Chain the proof is about to be submitted to.
+    ///
+    /// # Errors
+    /// Returns `MaldaError::TargetNetworkMismatch` if the chain is unknown or on another network.
+    pub fn ensure_destination(&self, chain_id: u64) -> Result<(), Error> {
+        if Chain::from_id(chain_id).map(|chain| chain.network()) != Some(self.network) {
+            return Err(MaldaError::TargetNetworkMismatch {
+                network: self.network,
+                target_chain_id: chain_id,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Errors returned by the proof data functions that callers are expected to handle.
+///
+/// These are wrapped in `anyhow::Error`; use `downcast_ref::<MaldaError>()` to match on them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaldaError {
+    /// The executor ran past the configured session cycle limit.
+    SessionLimitExceeded { session_limit: Option<u64> },
+    /// Building the ZKVM input for a chain took longer than the allowed timeout.
+    InputTimeout { chain_id: u64, timeout: Duration },
+    /// A batch mixed mainnet and Sepolia chains, which no single verifier accepts.
+    MixedNetworks { chain_ids: Vec<u64> },
+    /// A batch has no chains, so there is no network to prove it for.
+    EmptyBatch,
+    /// A batch or target chain ID is not a supported chain.
+    UnsupportedChain { chain_id: u64 },
+    /// A target chain belongs to a different network than the batch's source chains.
+    TargetNetworkMismatch {
+        network: Network,
+        target_chain_id: u64,
+    },
+    /// `RISC0_DEV_MODE` is set but the crate was built without the `dev-mode` feature.
+    DevModeNotAllowed,
+    /// No finalized OpStack dispute game is old enough to anchor an L1 inclusion proof yet.
+    NoMatureDisputeGame { chain_id: u64 },
+    /// A caller-supplied sequencer commitment is not signed by the chain's sequencer.
+    InvalidSequencerCommitment { chain_id: u64 },
+    /// Building or preflighting the view call environment kept failing.
+    PreflightFailed { chain_id: u64, attempts: u32 },
+    /// The Optimism and Base heads kept referencing different L1 blocks under dual L1
+    /// attestation.
+    L1OriginMismatch { attempts: u32 },
+    /// A Bonsai request or proving workflow failed.
+    Bonsai {
+        kind: BonsaiErrorKind,
+        message: String,
+    },
+}
+
+impl MaldaError {
+    /// Returns whether retrying the same request can't succeed.
+    ///
+    /// Invalid batches, rejected credentials and failed guest executions fail the same way
+    /// every time, while timeouts and network or server errors may clear up on a retry.
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            MaldaError::SessionLimitExceeded { .. }
+            | MaldaError::MixedNetworks { .. }
+            | MaldaError::EmptyBatch
+            | MaldaError::UnsupportedChain { .. }
+            | MaldaError::TargetNetworkMismatch { .. }
+            | MaldaError::DevModeNotAllowed
+            | MaldaError::InvalidSequencerCommitment { .. } => true,
+            MaldaError::InputTimeout { .. }
+            | MaldaError::NoMatureDisputeGame { .. }
+            | MaldaError::PreflightFailed { .. }
+            | MaldaError::L1OriginMismatch { .. } => false,
+            MaldaError::Bonsai { kind, .. } => *kind == BonsaiErrorKind::Permanent,
+        }
+    }
+}
+
+impl std::fmt::Display for MaldaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaldaError::SessionLimitExceeded { session_limit } => match session_limit {
+                Some(limit) => write!(f, "session limit of {} cycles exceeded", limit),
+                None => write!(f, "default session limit exceeded"),
+            },
+            MaldaError::InputTimeout { chain_id, timeout } => write!(
+                f,
+                "building input for chain {} timed out after {:?}",
+                chain_id, timeout
+            ),
+            MaldaError::MixedNetworks { chain_ids } => {
+                write!(f, "batch mixes mainnet and sepolia chains: {:?}", chain_ids)
+            }
+            MaldaError::EmptyBatch => write!(f, "batch has no chains"),
+            MaldaError::UnsupportedChain { chain_id } => {
+                write!(f, "chain {} is not supported", chain_id)
+            }
+            MaldaError::TargetNetworkMismatch {
+                network,
+                target_chain_id,
+            } => write!(
+                f,
+                "target chain {} is not on the batch network {:?}",
+                target_chain_id, network
+            ),
+            MaldaError::NoMatureDisputeGame { chain_id } => write!(
+                f,
+                "no mature finalized dispute game available for chain {}; try again later",
+                chain_id
+            ),
+            MaldaError::InvalidSequencerCommitment { chain_id } => write!(
+                f,
+                "supplied sequencer commitment for chain {} failed verification",
+                chain_id
+            ),
+            MaldaError::PreflightFailed { chain_id, attempts } => write!(
+                f,
+                "preflight for chain {} failed after {} attempts",
+                chain_id, attempts
+            ),
+            MaldaError::L1OriginMismatch { attempts } => write!(
+                f,
+                "optimism and base L1 origins did not match after {} attempts",
+                attempts
+            ),
+            MaldaError::DevModeNotAllowed => write!(
+                f,
+                "RISC0_DEV_MODE is set but malda_rs was built without the dev-mode feature"
+            ),
+            MaldaError::Bonsai { kind, message } => {
+                write!(f, "bonsai request failed ({:?}): {}", kind, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MaldaError {}
+
+/// Whether a failed Bonsai request can succeed when retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BonsaiErrorKind {
+    /// Network failures, server errors and timed out workflows.
+    Transient,
+    /// Missing or rejected credentials, exhausted quota and failed guest executions.
+    Permanent,
+}
+
+/// Bonsai error responses that won't change on a retry, matched case-insensitively.
+const PERMANENT_BONSAI_ERRORS: [&str; 5] =
+    ["quota", "unauthorized", "forbidden", "api key", "not found"];
+
+/// Classifies a Bonsai SDK error as transient or permanent.
+///
+/// The SDK reports error responses by their body only, so these are classified by the
+/// messages Bonsai uses for rejected credentials, exhausted quota and unknown images.
+///
+/// # Arguments
+/// * `err` - Error returned by the Bonsai client.
+///
+/// # Returns
+/// * `BonsaiErrorKind` - Whether retrying the request can succeed.
+pub fn classify_bonsai_error(err: &bonsai_sdk::SdkErr) -> BonsaiErrorKind {
+    match err {
+        bonsai_sdk::SdkErr::MissingApiKey
+        | bonsai_sdk::SdkErr::MissingApiUrl
+        | bonsai_sdk::SdkErr::HttpHeaderErr(_) => BonsaiErrorKind::Permanent,
+        bonsai_sdk::SdkErr::HttpErr(err) => {
+            if err
+                .status()
+                .is_some_and(|status| status.is_client_error() && status.as_u16() != 429)
+            {
+                BonsaiErrorKind::Permanent
+            } else {
+                BonsaiErrorKind::Transient
+            }
+        }
+        bonsai_sdk::SdkErr::InternalServerErr(body) => {
+            let body = body.to_lowercase();
+            if PERMANENT_BONSAI_ERRORS
+                .iter()
+                .any(|pattern| body.contains(pattern))
+            {
+                BonsaiErrorKind::Permanent
+            } else {
+                BonsaiErrorKind::Transient
+            }
+        }
+        _ => BonsaiErrorKind::Transient,
+    }
+}
+
+/// Wraps a Bonsai SDK error into a classified `MaldaError::Bonsai`.
+fn bonsai_error(err: bonsai_sdk::SdkErr) -> Error {
+    MaldaError::Bonsai {
+        kind: classify_bonsai_error(&err),
+        message: err.to_string(),
+    }
+    .into()
+}
+
+/// Creates the Bonsai client with `BONSAI_API_URL` and `BONSAI_API_KEY` from the secret source.
+///
+/// Falls back to the SDK's own environment lookup if either is missing, so its error reports
+/// what is missing.
+fn bonsai_client() -> Result<Client, Error> {
+    let (Some(url), Some(key)) = (secret("BONSAI_API_URL")?, secret("BONSAI_API_KEY")?) else {
+        return Client::from_env(risc0_zkvm::VERSION).map_err(bonsai_error);
+    };
+    Client::from_parts(url, key, risc0_zkvm::VERSION).map_err(bonsai_error)
+}
+
+/// Builds the error for a Bonsai workflow that exited without success.
+///
+/// Only timed out workflows are worth retrying; a failed workflow means the guest itself
+/// failed on this input, and an aborted one was cancelled on purpose.
+fn bonsai_workflow_error(uuid: &str, status: &str, error_msg: Option<String>) -> Error {
+    let kind = if status == "TIMED_OUT" {
+        BonsaiErrorKind::Transient
+    } else {
+        BonsaiErrorKind::Permanent
+    };
+    MaldaError::Bonsai {
+        kind,
+        message: format!(
+            "Bonsai prover workflow [{}] exited: {} err: {}",
+            uuid,
+            status,
+            error_msg.unwrap_or("Bonsai workflow missing error_msg".into())
+        ),
+    }
+    .into()
+}
+
+/// Optional inputs for building a single chain's ZKVM input.
+#[derive(Debug, Clone)]
+pub struct ZkvmInputOptions {
+    /// Sequencer commitment to use instead of fetching the current one.
+    ///
+    /// It must belong to the chain whose commitment the input would otherwise fetch: the
+    /// queried OpStack chain itself, or Optimism when anchoring to L1. It is verified before
+    /// use and ignored for chains that don't need a commitment.
+    pub sequencer_commitment: Option<SequencerCommitment>,
+    /// Extra blocks to query below the reorg protection depth on the Ethereum path.
+    ///
+    /// Ethereum proofs anchor to the L1 block reported by the OpStack `L1Block` predeploy,
+    /// which a small L1 reorg can still replace. A buffer queries correspondingly older state,
+    /// linked to the anchor by more linking blocks; the journal's `reorg_protection_depth`
+    /// reports the total. Ignored for other chains.
+    pub ethereum_reorg_buffer: u64,
+    /// Whether L1 inclusion is attested through both Optimism and Base.
+    ///
+    /// `ZkvmInputOptions::default` reads it from `DUAL_OPSTACK_L1_ATTESTATION`, see
+    /// `dual_opstack_l1_attestation`.
+    pub dual_opstack_l1_attestation: bool,
+}
+
+impl Default for ZkvmInputOptions {
+    fn default() -> Self {
+        Self {
+            sequencer_commitment: None,
+            ethereum_reorg_buffer: 0,
+            dual_opstack_l1_attestation: dual_opstack_l1_attestation(),
+        }
+    }
+}
+
+/// Resource limits applied to the executor environment.
+///
+/// `None` keeps the zkVM default for that limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecutorLimits {
+    /// Maximum number of cycles for the whole session.
+    pub session_limit: Option<u64>,
+    /// Maximum segment size as a power of two of cycles.
+    pub segment_limit_po2: Option<u32>,
+}
+
+/// Returns whether proofs are faked through `RISC0_DEV_MODE`.
+///
+/// Dev mode is only honored when the crate is built with the `dev-mode` feature, so a
+/// production build can never hand out fake receipts.
+///
+/// # Returns
+/// * `Result<bool, Error>` - `true` if dev mode is active.
+///
+/// # Errors
+/// Returns `MaldaError::DevModeNotAllowed` if `RISC0_DEV_MODE` is set without the feature.
+pub fn dev_mode() -> Result<bool, Error> {
+    if !risc0_zkvm::is_dev_mode() {
+        return Ok(false);
+    }
+    if cfg!(feature = "dev-mode") {
+        Ok(true)
+    } else {
+        Err(MaldaError::DevModeNotAllowed.into())
+    }
+}
+
+/// Executes the get proof data guest on a caller-built environment.
+///
+/// For integrations that build the guest input themselves, e.g. from cached fixtures or their
+/// own RPC layer. The input must follow the layout of `get_proof_data_input`.
+///
+/// # Arguments
+/// * `env` - Executor environment holding the guest input.
+///
+/// # Returns
+/// * `Result<SessionInfo, Error>` - Session info from the ZKVM execution.
+///
+/// # Errors
+/// Returns an error if ZKVM execution fails.
+pub fn exec_env(env: ExecutorEnv<'_>) -> Result<SessionInfo, Error> {
+    default_executor()
+        .execute(env, GET_PROOF_DATA_ELF)
+        .context("Failed to execute ZKVM")
+}
+
+/// Proves the get proof data guest on a caller-built environment.
+///
+/// See `exec_env`. Proving is CPU-bound and blocking; call it from a blocking task in async
+/// code.
+///
+/// # Arguments
+/// * `env` - Executor environment holding the guest input.
+/// * `opts` - Prover options, e.g. `ProverOpts::groth16()` for on-chain verification.
+///
+/// # Returns
+/// * `Result<ProveInfo, Error>` - Proof information from the ZKVM.
+///
+/// # Errors
+/// Returns an error if:
+/// - `RISC0_DEV_MODE` is set without the `dev-mode` feature (`MaldaError::DevModeNotAllowed`).
+/// - Proof generation fails.
+pub fn prove_env(env: ExecutorEnv<'_>, opts: ProverOpts) -> Result<ProveInfo, Error> {
+    dev_mode()?;
+    default_prover()
+        .prove_with_opts(env, GET_PROOF_DATA_ELF, &opts)
+        .context("Failed to prove proof data")
+}
+
+/// Checks that the embedded guest ELFs hash to the image IDs shipped alongside them.
+///
+/// `elfs_ids.rs` pairs every ELF with its image ID. If a stale binary was copied, the
+/// executor would run one guest while verifiers expect the other's ID and every proof would
+/// fail on chain, so this is meant to run once at startup.
+///
+/// # Returns
+/// * `Result<(), Error>` - Ok if every embedded ELF matches its image ID.
+///
+/// # Errors
+/// Returns an error if an ELF is empty because the guest wasn't built, can't be parsed, or
+/// its image ID differs from the constant.
+pub fn verify_elf_id_consistency() -> Result<(), Error> {
+    for (name, elf, image_id) in [
+        ("get-proof-data", GET_PROOF_DATA_ELF, GET_PROOF_DATA_ID),
+        (
+            "get_proof_data_ethereum_light_client",
+            GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ELF,
+            GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ID,
+        ),
+    ] {
+        if elf.is_empty() {
+            anyhow::bail!(
+                "embedded {} ELF is empty, build methods to regenerate elfs_ids.rs",
+                name
+            );
+        }
+        let computed = risc0_zkvm::compute_image_id(elf)
+            .with_context(|| format!("Failed to compute image ID of {}", name))?;
+        if computed != Digest::from(image_id) {
+            anyhow::bail!(
+                "embedded {} ELF has image ID {} but {} is expected",
+                name,
+                computed,
+                Digest::from(image_id)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Proves the input locally with a fake receipt in place of a Bonsai session.
+///
+/// # Arguments
+/// * `input_data` - The serialized input data for the ZKVM session.
+/// * `receipt_kind` - Kind of receipt to produce.
+/// * `network` - Network of the proven chains.
+///
+/// # Returns
+/// * `Result<MaldaProveInfo, anyhow::Error>` - Proof information shaped like a Bonsai result.
+///
+/// # Errors
+/// Returns an error if execution fails or the seal cannot be encoded.
+fn run_dev_mode(
+    input_data: Vec<u8>,
+    receipt_kind: ReceiptKind,
+    network: Network,
+) -> Result<MaldaProveInfo, anyhow::Error> {
+    run_local(input_data, receipt_kind, "dev-mode", network)
+}
+
+/// Proves the proof data guest on the local machine.
+///
+/// # Arguments
+/// * `input_data` - The serialized input data for the ZKVM session.
+/// * `receipt_kind` - Kind of receipt to produce.
+/// * `uuid` - Session identifier reported in place of a Bonsai session UUID.
+/// * `network` - Network of the proven chains.
+///
+/// # Returns
+/// * `Result<MaldaProveInfo, anyhow::Error>` - Proof information shaped like a Bonsai result.
+///
+/// # Errors
+/// Returns an error if proving fails or the seal cannot be encoded.
+fn run_local(
+    input_data: Vec<u8>,
+    receipt_kind: ReceiptKind,
+    uuid: &str,
+    network: Network,
+) -> Result<MaldaProveInfo, anyhow::Error> {
+    let env = ExecutorEnv::builder()
+        .write_slice(&input_data)
+        .build()
+        .context("Failed to build executor environment")?;
+
+    let opts = match receipt_kind {
+        ReceiptKind::Groth16 => ProverOpts::groth16(),
+        ReceiptKind::Succinct => ProverOpts::succinct(),
+    };
+    // `default_prover` would pick Bonsai whenever its credentials or `RISC0_PROVER` are set, so
+    // prove through `r0vm` explicitly. It still honors `RISC0_DEV_MODE`.
+    let r0vm_path = dotenvy::var("RISC0_SERVER_PATH").unwrap_or_else(|_| "r0vm".to_string());
+    let start_time = std::time::Instant::now();
+    let prove_info = ExternalProver::new("local", r0vm_path)
+        .prove_with_opts(env, GET_PROOF_DATA_ELF, &opts)
+        .context("Failed to prove proof data locally")?;
+    let prove_time = start_time.elapsed();
+
+    let seal_bytes = match receipt_kind {
+        ReceiptKind::Groth16 => risc0_ethereum_contracts::encode_seal(&prove_info.receipt)?,
+        ReceiptKind::Succinct => vec![],
+    };
+    let journal_bytes = prove_info.receipt.journal.bytes.clone();
+
+    Ok(MaldaProveInfo {
+        receipt: prove_info.receipt,
+        receipt_kind,
+        stats: MaldaSessionStats {
+            segments: prove_info.stats.segments,
+            total_cycles: prove_info.stats.total_cycles,
+            user_cycles: prove_info.stats.user_cycles,
+            paging_cycles: prove_info.stats.paging_cycles,
+            reserved_cycles: prove_info.stats.reserved_cycles,
+        },
+        uuid: uuid.to_string(),
+        stark_time: prove_time.as_secs(),
+        snark_time: 0,
+        seal_bytes,
+        journal_bytes,
+        network,
+    })
 }
 
 /// Runs a Bonsai ZK proof session with the provided input data.
 ///
+/// The SNARK wrap is skipped when a succinct receipt is requested.
+///
 /// # Arguments
 /// * `input_data` - The serialized input data for the ZKVM session.
+/// * `receipt_kind` - Kind of receipt to produce.
+/// * `network` - Network of the proven chains.
 ///
 /// # Returns
 /// * `Result<MaldaProveInfo, anyhow::Error>` - Proof information and statistics if successful, or an error.
@@ -92,27 +612,35 @@ pub struct MaldaProveInfo {
 /// - The SNARK proof or receipt download fails.
 /// - The receipt cannot be deserialized.
 ///
+/// Bonsai failures are returned as `MaldaError::Bonsai`, classified by `classify_bonsai_error`.
+///
 /// # Panics
 /// Panics if the required environment variable `IMAGE_ID_BONSAI` is not set.
-fn run_bonsai(input_data: Vec<u8>) -> Result<MaldaProveInfo, anyhow::Error> {
+fn run_bonsai(
+    input_data: Vec<u8>,
+    receipt_kind: ReceiptKind,
+    network: Network,
+) -> Result<MaldaProveInfo, anyhow::Error> {
 
-    let client = Client::from_env(risc0_zkvm::VERSION)?;
+    let client = bonsai_client()?;
 
     let image_id_hex: String = dotenvy::var("IMAGE_ID_BONSAI")
         .expect("IMAGE_ID_BONSAI must be set in environment");
 
-    let input_id = client.upload_input(input_data)?;
+    let input_id = client.upload_input(input_data).map_err(bonsai_error)?;
 
     let assumptions: Vec<String> = vec![];
     let execute_only = false;
 
-    let session = client.create_session(image_id_hex, input_id, assumptions, execute_only)?;
+    let session = client
+        .create_session(image_id_hex, input_id, assumptions, execute_only)
+        .map_err(bonsai_error)?;
 
     let polling_interval = Duration::from_millis(500);
 
     let stark_time = std::time::Instant::now();
     let succinct_stats = loop {
-        let res = session.status(&client)?;
+        let res = session.status(&client).map_err(bonsai_error)?;
         if res.status == "RUNNING" {
             std::thread::sleep(polling_interval);
             continue;
@@ -136,21 +664,40 @@ fn run_bonsai(input_data: Vec<u8>) -> Result<MaldaProveInfo, anyhow::Error> {
                 reserved_cycles: 0,
             };
         } else {
-            return Err(anyhow::Error::msg(format!(
-                "Bonsai prover workflow [{}] exited: {} err: {}",
-                session.uuid,
-                res.status,
-                res.error_msg
-                    .unwrap_or("Bonsai workflow missing error_msg".into())
-            )));
+            return Err(bonsai_workflow_error(
+                &session.uuid,
+                &res.status,
+                res.error_msg,
+            ));
         }
     };
     let stark_time = stark_time.elapsed();
-    let snark_session = client.create_snark(session.uuid.clone())?;
+
+    if receipt_kind == ReceiptKind::Succinct {
+        let receipt_buf = client.receipt_download(&session).map_err(bonsai_error)?;
+        let succinct_receipt: Receipt = bincode::deserialize(&receipt_buf)?;
+        let journal_bytes = succinct_receipt.journal.bytes.clone();
+
+        return Ok(MaldaProveInfo {
+            receipt: succinct_receipt,
+            receipt_kind,
+            stats: succinct_stats,
+            uuid: session.uuid,
+            stark_time: stark_time.as_secs(),
+            snark_time: 0,
+            seal_bytes: vec![],
+            journal_bytes,
+            network,
+        });
+    }
+
+    let snark_session = client
+        .create_snark(session.uuid.clone())
+        .map_err(bonsai_error)?;
 
     let start = std::time::Instant::now();
     let snark_receipt_url = loop {
-        let res = snark_session.status(&client)?;
+        let res = snark_session.status(&client).map_err(bonsai_error)?;
         match res.status.as_str() {
             "RUNNING" => {
                 std::thread::sleep(polling_interval);
@@ -165,29 +712,39 @@ fn run_bonsai(input_data: Vec<u8>) -> Result<MaldaProveInfo, anyhow::Error> {
                 })?;
             }
             _ => {
-                return Err(anyhow::Error::msg(format!(
-                    "Bonsai prover workflow [{}] exited: {} err: {}",
-                    snark_session.uuid,
-                    res.status,
-                    res.error_msg
-                        .unwrap_or("Bonsai workflow missing error_msg".into())
-                )));
+                return Err(bonsai_workflow_error(
+                    &snark_session.uuid,
+                    &res.status,
+                    res.error_msg,
+                ));
             }
         }
     };
 
     let snark_time = start.elapsed();
 
-    let receipt_buf = client.download(&snark_receipt_url)?;
+    let receipt_buf = client.download(&snark_receipt_url).map_err(bonsai_error)?;
     let groth16_receipt: Receipt = bincode::deserialize(&receipt_buf)?;
 
+    let seal_bytes = risc0_ethereum_contracts::encode_seal(&groth16_receipt)?;
+    let journal_bytes = groth16_receipt.journal.bytes.clone();
+    info!(
+        "Bonsai session {} groth16 seal length: {} bytes, journal length: {} bytes",
+        session.uuid,
+        seal_bytes.len(),
+        journal_bytes.len()
+    );
 
     Ok(MaldaProveInfo {
         receipt: groth16_receipt,
+        receipt_kind,
         stats: succinct_stats,
         uuid: session.uuid,
         stark_time: stark_time.as_secs(),
         snark_time: snark_time.as_secs(),
+        seal_bytes,
+        journal_bytes,
+        network,
     })
 }
 
@@ -198,7 +755,8 @@ fn run_bonsai(input_data: Vec<u8>) -> Result<MaldaProveInfo, anyhow::Error> {
 /// * `markets` - Vector of market contract address vectors, one per chain.
 /// * `target_chain_id` - Vector of target chain IDs to query (vector of vectors).
 /// * `chain_ids` - Vector of chain IDs to query.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
 ///
 /// # Returns
 /// * `Result<SessionInfo, Error>` - Session info from the ZKVM execution.
@@ -213,131 +771,248 @@ pub async fn get_proof_data_exec(
     markets: Vec<Vec<Address>>,
     target_chain_id: Vec<Vec<u64>>,
     chain_ids: Vec<u64>,
-    l1_inclusion: bool,
+    safety: impl Into<BlockSafety>,
 ) -> Result<SessionInfo, Error> {
+    get_proof_data_exec_with_limits(
+        users,
+        markets,
+        target_chain_id,
+        chain_ids,
+        safety,
+        ExecutorLimits::default(),
+    )
+    .await
+}
 
-    assert_eq!(
-        users.len(),
-        markets.len(),
-        "Users and markets array lengths must match"
-    );
-    assert_eq!(
-        users.len(),
-        chain_ids.len(),
-        "Users and chain_ids array lengths must match"
-    );
-
-    let futures: Vec<_> = (0..chain_ids.len())
-        .map(|i| {
-            let users = users[i].clone();
-            let markets = markets[i].clone();
-            let target_chain_id = target_chain_id[i].clone();
-            let chain_id = chain_ids[i];
-            tokio::spawn(async move {
-                get_proof_data_zkvm_input(users, markets, target_chain_id, chain_id, l1_inclusion)
-                    .await
-            })
-        })
-        .collect();
-
-    let results = join_all(futures).await;
-    let all_inputs = results
-        .into_iter()
-        .map(|r| r.expect("Failed to join parallel execution task"))
-        .flatten()
-        .collect::<Vec<u8>>();
+/// Executes proof data queries across multiple chains in parallel with explicit executor limits.
+///
+/// # Arguments
+/// * `users` - Vector of user address vectors, one per chain.
+/// * `markets` - Vector of market contract address vectors, one per chain.
+/// * `target_chain_id` - Vector of target chain IDs to query (vector of vectors).
+/// * `chain_ids` - Vector of chain IDs to query.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+/// * `limits` - Session and segment limits for the executor.
+///
+/// # Returns
+/// * `Result<SessionInfo, Error>` - Session info from the ZKVM execution.
+///
+/// # Errors
+/// Returns an error if:
+/// - Array lengths don't match.
+/// - RPC calls fail.
+/// - The batch mixes mainnet and Sepolia chains (`MaldaError::MixedNetworks`).
+/// - A target chain is on another network (`MaldaError::TargetNetworkMismatch`).
+/// - Building a chain's input times out (`MaldaError::InputTimeout`).
+/// - The session exceeds `limits.session_limit` (`MaldaError::SessionLimitExceeded`).
+/// - ZKVM execution fails.
+pub async fn get_proof_data_exec_with_limits(
+    users: Vec<Vec<Address>>,
+    markets: Vec<Vec<Address>>,
+    target_chain_id: Vec<Vec<u64>>,
+    chain_ids: Vec<u64>,
+    safety: impl Into<BlockSafety>,
+    limits: ExecutorLimits,
+) -> Result<SessionInfo, Error> {
+    let safety = safety.into();
+    let input = get_proof_data_input(users, markets, target_chain_id, chain_ids, safety).await?;
+    exec_input_with_limits(&input, limits)
+}
 
-    let env = ExecutorEnv::builder()
-        .write(&(chain_ids.len() as u64))
-        .expect("Failed to write chain count to executor environment")
-        .write_slice(&all_inputs)
+/// Executes the proof data guest on a prepared input with explicit executor limits.
+///
+/// # Arguments
+/// * `input` - Serialized guest input, e.g. from `get_proof_data_input`.
+/// * `limits` - Session and segment limits for the executor.
+///
+/// # Returns
+/// * `Result<SessionInfo, Error>` - Session info from the ZKVM execution.
+///
+/// # Errors
+/// Returns an error if:
+/// - The executor environment can't be built.
+/// - The session exceeds `limits.session_limit` (`MaldaError::SessionLimitExceeded`).
+/// - ZKVM execution fails.
+pub fn exec_input_with_limits(input: &[u8], limits: ExecutorLimits) -> Result<SessionInfo, Error> {
+    let mut builder = ExecutorEnv::builder();
+    builder.write_slice(input);
+    if let Some(session_limit) = limits.session_limit {
+        builder.session_limit(Some(session_limit));
+    }
+    if let Some(segment_limit_po2) = limits.segment_limit_po2 {
+        builder.segment_limit_po2(segment_limit_po2);
+    }
+    let env = builder
         .build()
-        .expect("Failed to build executor environment");
+        .context("Failed to build executor environment")?;
 
-    Ok(default_executor()
+    // The executor reports the limit only in its error text, "Session limit exceeded: ...".
+    default_executor()
         .execute(env, GET_PROOF_DATA_ELF)
-        .expect("Failed to execute ZKVM"))
+        .map_err(|err| {
+            if err
+                .to_string()
+                .to_lowercase()
+                .contains("session limit exceeded")
+            {
+                MaldaError::SessionLimitExceeded {
+                    session_limit: limits.session_limit,
+                }
+                .into()
+            } else {
+                err.context("Failed to execute ZKVM")
+            }
+        })
 }
 
-/// Creates the executor environment with proof data from multiple chains.
+/// Estimates the cycles of proving a batch by running only the executor.
+///
+/// Bonsai bills by cycles, so this gives the approximate cost of a proof before requesting
+/// it. Nothing is proven.
 ///
 /// # Arguments
 /// * `users` - Vector of user address vectors, one per chain.
 /// * `markets` - Vector of market contract address vectors, one per chain.
 /// * `target_chain_ids` - Vector of target chain IDs to query (vector of vectors).
 /// * `chain_ids` - Vector of chain IDs to query.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
 ///
 /// # Returns
-/// * `ExecutorEnv<'static>` - Environment configured with proof data inputs.
+/// * `Result<MaldaSessionStats, Error>` - Segment and cycle counts of the execution.
+///   `total_cycles` is the sum of the padded segment sizes; the executor doesn't break the
+///   overhead down, so `paging_cycles` and `reserved_cycles` are `0`.
 ///
-/// # Panics
-/// Panics if:
-/// - Array lengths don't match.
-async fn get_proof_data_env(
+/// # Errors
+/// See `get_proof_data_exec`.
+pub async fn estimate_proof_cycles(
     users: Vec<Vec<Address>>,
     markets: Vec<Vec<Address>>,
     target_chain_ids: Vec<Vec<u64>>,
     chain_ids: Vec<u64>,
-    l1_inclusion: bool,
-) -> ExecutorEnv<'static> {
+    safety: impl Into<BlockSafety>,
+) -> Result<MaldaSessionStats, Error> {
+    let session_info =
+        get_proof_data_exec(users, markets, target_chain_ids, chain_ids, safety).await?;
+
+    Ok(MaldaSessionStats {
+        segments: session_info.segments.len(),
+        total_cycles: session_info.segments.iter().map(|s| 1u64 << s.po2).sum(),
+        user_cycles: session_info.segments.iter().map(|s| s.cycles as u64).sum(),
+        paging_cycles: 0,
+        reserved_cycles: 0,
+    })
+}
 
-    assert_eq!(users.len(), markets.len());
-    assert_eq!(users.len(), chain_ids.len());
+/// Checks that all chains in a batch belong to the same network.
+///
+/// # Arguments
+/// * `chain_ids` - Chain IDs of the batch.
+///
+/// # Returns
+/// * `Result<Network, Error>` - The shared network of the batch.
+///
+/// # Errors
+/// Returns an error if:
+/// - The batch is empty (`MaldaError::EmptyBatch`).
+/// - A chain ID is not supported (`MaldaError::UnsupportedChain`).
+/// - Mainnet and Sepolia chains are mixed (`MaldaError::MixedNetworks`).
+pub fn ensure_single_network(chain_ids: &[u64]) -> Result<Network, Error> {
+    let first_chain_id = *chain_ids.first().ok_or(MaldaError::EmptyBatch)?;
+    let network = chain_network(first_chain_id)?;
+    for chain_id in chain_ids {
+        if chain_network(*chain_id)? != network {
+            return Err(MaldaError::MixedNetworks {
+                chain_ids: chain_ids.to_vec(),
+            }
+            .into());
+        }
+    }
+    Ok(network)
+}
 
-    let futures: Vec<_> = (0..chain_ids.len())
-        .map(|i| {
-            let users = users[i].clone();
-            let markets = markets[i].clone();
-            let chain_id = chain_ids[i];
-            let target_chain_id = target_chain_ids[i].clone();
-            tokio::spawn(async move {
-                get_proof_data_zkvm_input(users, markets, target_chain_id, chain_id, l1_inclusion)
-                    .await
-            })
-        })
-        .collect();
+/// Returns the network of a chain, or `MaldaError::UnsupportedChain` for unknown chains.
+fn chain_network(chain_id: u64) -> Result<Network, MaldaError> {
+    Chain::from_id(chain_id)
+        .map(|chain| chain.network())
+        .ok_or(MaldaError::UnsupportedChain { chain_id })
+}
 
-    let results = join_all(futures).await;
-    let all_inputs = results
-        .into_iter()
-        .filter_map(|r| r.ok())
-        .flat_map(|input| input)
-        .collect::<Vec<_>>();
-
-    ExecutorEnv::builder()
-        .write(&(chain_ids.len() as u64))
-        .unwrap()
-        .write_slice(&all_inputs)
-        .build()
-        .unwrap()
+/// Checks that every target chain of a batch is on the batch's network.
+///
+/// The proof is verified on the target chains, so a target on another network could
+/// never accept it.
+///
+/// # Arguments
+/// * `network` - Network of the batch's source chains.
+/// * `target_chain_ids` - Target chain IDs of the batch (vector of vectors).
+///
+/// # Errors
+/// Returns an error for the first target that:
+/// - Is not a supported chain (`MaldaError::UnsupportedChain`).
+/// - Is on another network (`MaldaError::TargetNetworkMismatch`).
+pub fn ensure_targets_on_network(
+    network: Network,
+    target_chain_ids: &[Vec<u64>],
+) -> Result<(), Error> {
+    for target_chain_id in target_chain_ids.iter().flatten() {
+        if chain_network(*target_chain_id)? != network {
+            return Err(MaldaError::TargetNetworkMismatch {
+                network,
+                target_chain_id: *target_chain_id,
+            }
+            .into());
+        }
+    }
+    Ok(())
 }
 
 /// Prepares input data for the ZKVM for multiple chains' proof data queries.
 ///
+/// Each chain's input is bounded by `proof_data_input_timeout()`.
+///
 /// # Arguments
 /// * `users` - Vector of user address vectors, one per chain.
 /// * `markets` - Vector of market contract address vectors, one per chain.
 /// * `target_chain_ids` - Vector of target chain IDs to query (vector of vectors).
 /// * `chain_ids` - Vector of chain IDs to query.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`.
 ///
 /// # Returns
-/// * `Vec<u8>` - Serialized input data for the ZKVM.
+/// * `Result<Vec<u8>, Error>` - Serialized input data for the ZKVM.
+///
+/// # Errors
+/// Returns an error if:
+/// - The batch mixes mainnet and Sepolia chains (`MaldaError::MixedNetworks`).
+/// - A target chain is on another network (`MaldaError::TargetNetworkMismatch`).
+/// - A chain's input times out (`MaldaError::InputTimeout`).
+/// - A chain's input task fails.
 ///
 /// # Panics
 /// Panics if:
 /// - Array lengths don't match.
-async fn get_proof_data_input(
+pub(crate) async fn get_proof_data_input(
     users: Vec<Vec<Address>>,
     markets: Vec<Vec<Address>>,
     target_chain_ids: Vec<Vec<u64>>,
     chain_ids: Vec<u64>,
-    l1_inclusion: bool,
-) -> Vec<u8> {
+    safety: BlockSafety,
+) -> Result<Vec<u8>, Error> {
+    assert_eq!(
+        users.len(),
+        markets.len(),
+        "Users and markets array lengths must match"
+    );
+    assert_eq!(
+        users.len(),
+        chain_ids.len(),
+        "Users and chain_ids array lengths must match"
+    );
+    let network = ensure_single_network(&chain_ids)?;
+    ensure_targets_on_network(network, &target_chain_ids)?;
 
-    assert_eq!(users.len(), markets.len());
-    assert_eq!(users.len(), chain_ids.len());
+    let timeout = proof_data_input_timeout();
 
     let futures: Vec<_> = (0..chain_ids.len())
         .map(|i| {
@@ -346,24 +1021,102 @@ async fn get_proof_data_input(
             let chain_id = chain_ids[i];
             let target_chain_id = target_chain_ids[i].clone();
             tokio::spawn(async move {
-                get_proof_data_zkvm_input(users, markets, target_chain_id, chain_id, l1_inclusion)
-                    .await
+                get_proof_data_zkvm_input_with_timeout(
+                    users,
+                    markets,
+                    target_chain_id,
+                    chain_id,
+                    safety,
+                    timeout,
+                )
+                .await
             })
         })
         .collect();
 
     let results = join_all(futures).await;
-    let all_inputs = results
+    let mut all_inputs = Vec::new();
+    for (result, chain_id) in results.into_iter().zip(chain_ids.iter()) {
+        let input =
+            result.with_context(|| format!("Input task for chain {} failed", chain_id))??;
+        all_inputs.extend(input);
+    }
+
+    let input: Vec<u8> = bytemuck::pod_collect_to_vec(
+        &risc0_zkvm::serde::to_vec(&(chain_ids.len() as u64)).unwrap(),
+    );
+
+    Ok([input, all_inputs].concat())
+}
+
+/// Builds the contract call guest input for view calls across multiple chains.
+///
+/// Laid out like `get_proof_data_input`, with each chain's input built by
+/// `get_contract_call_zkvm_input`.
+///
+/// # Arguments
+/// * `calls` - View calls, one vector per chain.
+/// * `chain_ids` - Vector of chain IDs to call.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`.
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Serialized input data for the ZKVM.
+///
+/// # Errors
+/// Returns an error if:
+/// - The batch mixes mainnet and Sepolia chains (`MaldaError::MixedNetworks`).
+/// - A chain's input times out (`MaldaError::InputTimeout`).
+/// - A chain's input task fails.
+///
+/// # Panics
+/// Panics if the array lengths don't match.
+pub async fn get_contract_call_input(
+    calls: Vec<Vec<ContractCall>>,
+    chain_ids: Vec<u64>,
+    safety: BlockSafety,
+) -> Result<Vec<u8>, Error> {
+    assert_eq!(
+        calls.len(),
+        chain_ids.len(),
+        "Calls and chain_ids array lengths must match"
+    );
+    ensure_single_network(&chain_ids)?;
+
+    let timeout = proof_data_input_timeout();
+
+    let futures: Vec<_> = calls
         .into_iter()
-        .filter_map(|r| r.ok())
-        .flat_map(|input| input)
-        .collect::<Vec<_>>();
+        .zip(chain_ids.iter().copied())
+        .map(|(calls, chain_id)| {
+            tokio::spawn(async move {
+                tokio::time::timeout(
+                    timeout,
+                    get_contract_call_zkvm_input(
+                        calls,
+                        chain_id,
+                        safety,
+                        ZkvmInputOptions::default(),
+                    ),
+                )
+                .await
+                .map_err(|_| MaldaError::InputTimeout { chain_id, timeout })?
+            })
+        })
+        .collect();
+
+    let results = join_all(futures).await;
+    let mut all_inputs = Vec::new();
+    for (result, chain_id) in results.into_iter().zip(chain_ids.iter()) {
+        let input =
+            result.with_context(|| format!("Input task for chain {} failed", chain_id))??;
+        all_inputs.extend(input);
+    }
 
     let input: Vec<u8> = bytemuck::pod_collect_to_vec(
         &risc0_zkvm::serde::to_vec(&(chain_ids.len() as u64)).unwrap(),
     );
 
-    [input, all_inputs].concat()
+    Ok([input, all_inputs].concat())
 }
 
 /// Generates ZK proofs for proof data queries across multiple chains.
@@ -373,7 +1126,8 @@ async fn get_proof_data_input(
 /// * `markets` - Vector of market contract address vectors, one per chain.
 /// * `target_chain_ids` - Vector of target chain IDs to query (vector of vectors).
 /// * `chain_ids` - Vector of chain IDs to query.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
 ///
 /// # Returns
 /// * `Result<ProveInfo, Error>` - Proof information from the ZKVM.
@@ -382,120 +1136,366 @@ async fn get_proof_data_input(
 /// Returns an error if:
 /// - Array lengths don't match.
 /// - RPC calls fail.
+/// - `RISC0_DEV_MODE` is set without the `dev-mode` feature (`MaldaError::DevModeNotAllowed`).
 /// - Proof generation fails.
 pub async fn get_proof_data_prove(
     users: Vec<Vec<Address>>,
     markets: Vec<Vec<Address>>,
     target_chain_ids: Vec<Vec<u64>>,
     chain_ids: Vec<u64>,
-    l1_inclusion: bool,
+    safety: impl Into<BlockSafety>,
 ) -> Result<ProveInfo, Error> {
-
-    let prove_info = tokio::task::spawn_blocking(move || {
-
-        let rt = tokio::runtime::Runtime::new().unwrap();
-
-        let start_time = std::time::Instant::now();
-        let env = rt.block_on(get_proof_data_env(
-            users,
-            markets,
-            target_chain_ids,
-            chain_ids,
-            l1_inclusion,
-        ));
-        let duration = start_time.elapsed();
-        info!("Env creation time: {:?}", duration);
+    let safety = safety.into();
+    let start_time = std::time::Instant::now();
+    let input = get_proof_data_input(users, markets, target_chain_ids, chain_ids, safety).await?;
+    info!("Env creation time: {:?}", start_time.elapsed());
+
+    dev_mode()?;
+
+    // Only the CPU-bound proving runs on the blocking pool. The executor environment is
+    // built there because it is not `Send`.
+    tokio::task::spawn_blocking(move || {
+        let env = ExecutorEnv::builder()
+            .write_slice(&input)
+            .build()
+            .context("Failed to build executor environment")?;
 
         let start_time = std::time::Instant::now();
-        let proof =
-            default_prover().prove_with_opts(env, GET_PROOF_DATA_ELF, &ProverOpts::groth16());
-        let duration = start_time.elapsed();
-        info!("Bonsai proof time: {:?}", duration);
+        let proof = prove_env(env, ProverOpts::groth16());
+        info!("Local proof time: {:?}", start_time.elapsed());
         proof
     })
-    .await?;
+    .await
+    .context("Proving task failed to complete")?
+}
 
-    prove_info
+/// Generates Groth16 proofs for proof data queries across multiple chains using the Bonsai SDK.
+///
+/// # Arguments
+/// * `users` - Vector of user address vectors, one per chain.
+/// * `markets` - Vector of market contract address vectors, one per chain.
+/// * `target_chain_ids` - Vector of target chain IDs to query (vector of vectors).
+/// * `chain_ids` - Vector of chain IDs to query.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+///
+/// # Returns
+/// * `Result<MaldaProveInfo, Error>` - Proof information from the Bonsai SDK, or a fake
+///   local receipt when `dev_mode()` is active.
+///
+/// # Errors
+/// See `get_proof_data_prove_sdk_with_receipt_kind`.
+pub async fn get_proof_data_prove_sdk(
+    users: Vec<Vec<Address>>,
+    markets: Vec<Vec<Address>>,
+    target_chain_ids: Vec<Vec<u64>>,
+    chain_ids: Vec<u64>,
+    safety: impl Into<BlockSafety>,
+) -> Result<MaldaProveInfo, Error> {
+    get_proof_data_prove_sdk_with_receipt_kind(
+        users,
+        markets,
+        target_chain_ids,
+        chain_ids,
+        safety,
+        ReceiptKind::Groth16,
+    )
+    .await
 }
 
-/// Generates ZK proofs for proof data queries across multiple chains using the Bonsai SDK.
+/// Generates ZK proofs of the requested receipt kind for proof data queries across multiple
+/// chains using the Bonsai SDK.
 ///
 /// # Arguments
 /// * `users` - Vector of user address vectors, one per chain.
 /// * `markets` - Vector of market contract address vectors, one per chain.
 /// * `target_chain_ids` - Vector of target chain IDs to query (vector of vectors).
 /// * `chain_ids` - Vector of chain IDs to query.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+/// * `receipt_kind` - Groth16 for on-chain verification, or succinct to skip the SNARK wrap.
 ///
 /// # Returns
-/// * `Result<MaldaProveInfo, Error>` - Proof information from the Bonsai SDK.
+/// * `Result<MaldaProveInfo, Error>` - Proof information from the Bonsai SDK, or a fake
+///   local receipt when `dev_mode()` is active.
 ///
 /// # Errors
 /// Returns an error if:
 /// - Array lengths don't match.
 /// - RPC calls fail.
-/// - Proof generation fails.
-pub async fn get_proof_data_prove_sdk(
+/// - `RISC0_DEV_MODE` is set without the `dev-mode` feature (`MaldaError::DevModeNotAllowed`).
+/// - Proof generation fails. Bonsai failures are `MaldaError::Bonsai`; use
+///   `MaldaError::is_permanent` to decide whether to retry.
+pub async fn get_proof_data_prove_sdk_with_receipt_kind(
     users: Vec<Vec<Address>>,
     markets: Vec<Vec<Address>>,
     target_chain_ids: Vec<Vec<u64>>,
     chain_ids: Vec<u64>,
-    l1_inclusion: bool,
+    safety: impl Into<BlockSafety>,
+    receipt_kind: ReceiptKind,
 ) -> Result<MaldaProveInfo, Error> {
+    let safety = safety.into();
+    let network = ensure_single_network(&chain_ids)?;
+    let start_time = std::time::Instant::now();
+    let input = get_proof_data_input(users, markets, target_chain_ids, chain_ids, safety).await?;
+    info!("Env creation time: {:?}", start_time.elapsed());
 
-    let prove_info = tokio::task::spawn_blocking(move || {
-
-        let rt = tokio::runtime::Runtime::new().unwrap();
+    let fake_receipt = dev_mode()?;
 
+    // The Bonsai client is blocking, so polling runs on the blocking pool.
+    tokio::task::spawn_blocking(move || {
         let start_time = std::time::Instant::now();
-        let input = rt.block_on(get_proof_data_input(
-            users,
-            markets,
-            target_chain_ids,
-            chain_ids,
-            l1_inclusion,
-        ));
-        let duration = start_time.elapsed();
-        info!("Env creation time: {:?}", duration);
+        let (proof, prover) = if fake_receipt {
+            (run_dev_mode(input, receipt_kind, network), "Dev mode")
+        } else {
+            (
+                run_bonsai(input, receipt_kind, network).context("Bonsai proving failed"),
+                "Bonsai",
+            )
+        };
+        info!("{} proof time: {:?}", prover, start_time.elapsed());
+        proof
+    })
+    .await
+    .context("Proving task failed to complete")?
+}
 
+/// Generates Groth16 proofs for proof data queries across multiple chains on the given backend.
+///
+/// Both backends return the seal encoded with `encode_seal`, so callers can switch between
+/// Bonsai and local proving without changing how proofs are submitted.
+///
+/// # Arguments
+/// * `users` - Vector of user address vectors, one per chain.
+/// * `markets` - Vector of market contract address vectors, one per chain.
+/// * `target_chain_ids` - Vector of target chain IDs to query (vector of vectors).
+/// * `chain_ids` - Vector of chain IDs to query.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+/// * `backend` - Prover to use, see `ProvingBackend::from_env`.
+///
+/// # Returns
+/// * `Result<MaldaProveInfo, Error>` - Proof information, or a fake local receipt when
+///   `dev_mode()` is active.
+///
+/// # Errors
+/// See `get_proof_data_prove_sdk_with_receipt_kind`.
+pub async fn get_proof_data_prove_with_backend(
+    users: Vec<Vec<Address>>,
+    markets: Vec<Vec<Address>>,
+    target_chain_ids: Vec<Vec<u64>>,
+    chain_ids: Vec<u64>,
+    safety: impl Into<BlockSafety>,
+    backend: ProvingBackend,
+) -> Result<MaldaProveInfo, Error> {
+    if backend == ProvingBackend::Bonsai {
+        return get_proof_data_prove_sdk(users, markets, target_chain_ids, chain_ids, safety).await;
+    }
+
+    let safety = safety.into();
+    let network = ensure_single_network(&chain_ids)?;
+    let start_time = std::time::Instant::now();
+    let input = get_proof_data_input(users, markets, target_chain_ids, chain_ids, safety).await?;
+    info!("Env creation time: {:?}", start_time.elapsed());
+
+    let uuid = if dev_mode()? { "dev-mode" } else { "local" };
+
+    tokio::task::spawn_blocking(move || {
         let start_time = std::time::Instant::now();
-        let proof = run_bonsai(input);
-        let duration = start_time.elapsed();
-        info!("Bonsai proof time: {:?}", duration);
+        let proof = run_local(input, ReceiptKind::Groth16, uuid, network);
+        info!("Local proof time: {:?}", start_time.elapsed());
         proof
     })
-    .await?;
+    .await
+    .context("Local proving task failed to complete")?
+}
 
-    prove_info
+/// Prepares input data for the ZKVM for a single chain, bounded by a total timeout.
+///
+/// # Arguments
+/// * `users` - Vector of user addresses to query.
+/// * `markets` - Vector of market contract addresses to query.
+/// * `target_chain_ids` - Vector of target chain IDs to query, one per user.
+/// * `chain_id` - Chain ID for the queries.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+/// * `timeout` - Deadline for building the whole input.
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Serialized input data for the ZKVM.
+///
+/// # Errors
+/// Returns an error if:
+/// - The input isn't ready before `timeout` (`MaldaError::InputTimeout`).
+/// - Building the input fails (see `get_proof_data_zkvm_input`).
+pub async fn get_proof_data_zkvm_input_with_timeout(
+    users: Vec<Address>,
+    markets: Vec<Address>,
+    target_chain_ids: Vec<u64>,
+    chain_id: u64,
+    safety: impl Into<BlockSafety>,
+    timeout: Duration,
+) -> Result<Vec<u8>, Error> {
+    tokio::time::timeout(
+        timeout,
+        get_proof_data_zkvm_input(users, markets, target_chain_ids, chain_id, safety),
+    )
+    .await
+    .map_err(|_| MaldaError::InputTimeout { chain_id, timeout })?
 }
 
 /// Prepares input data for the ZKVM for a single chain's proof data queries.
 ///
 /// # Arguments
-/// * `users` - Vector of user addresses to query.
-/// * `markets` - Vector of market contract addresses to query.
-/// * `target_chain_ids` - Vector of target chain IDs to query.
-/// * `chain_id` - Chain ID for the queries.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `users` - Vector of user addresses to query.
+/// * `markets` - Vector of market contract addresses to query.
+/// * `target_chain_ids` - Vector of target chain IDs to query, one per user. Use
+///   `fan_out_target_chains` to query a user toward several target chains.
+/// * `chain_id` - Chain ID for the queries.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Serialized input data for the ZKVM.
+///
+/// # Errors
+/// Returns an error if:
+/// - An OpStack L1 inclusion proof has no mature finalized dispute game to anchor to yet
+///   (`MaldaError::NoMatureDisputeGame`).
+/// - With dual L1 attestation, Optimism and Base never share an L1 origin within
+///   `DUAL_OPSTACK_L1_ATTESTATION_MAX_ATTEMPTS` attempts (`MaldaError::L1OriginMismatch`).
+///
+/// # Panics
+/// Panics if:
+/// - Invalid chain ID is provided.
+/// - RPC calls fail.
+pub async fn get_proof_data_zkvm_input(
+    users: Vec<Address>,
+    markets: Vec<Address>,
+    target_chain_ids: Vec<u64>,
+    chain_id: u64,
+    safety: impl Into<BlockSafety>,
+) -> Result<Vec<u8>, Error> {
+    get_proof_data_zkvm_input_with_options(
+        users,
+        markets,
+        target_chain_ids,
+        chain_id,
+        safety,
+        ZkvmInputOptions::default(),
+    )
+    .await
+}
+
+/// Prepares input data for the ZKVM for a single chain's proof data queries, using pre-fetched data.
+///
+/// # Arguments
+/// * `users` - Vector of user addresses to query.
+/// * `markets` - Vector of market contract addresses to query.
+/// * `target_chain_ids` - Vector of target chain IDs to query, one per user. Use
+///   `fan_out_target_chains` to query a user toward several target chains.
+/// * `chain_id` - Chain ID for the queries.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+/// * `options` - Pre-fetched data to use instead of querying it.
+///
+/// # Returns
+/// * `Result<Vec<u8>, Error>` - Serialized input data for the ZKVM.
+///
+/// # Errors
+/// Returns an error if:
+/// - An OpStack L1 inclusion proof has no mature finalized dispute game to anchor to yet
+///   (`MaldaError::NoMatureDisputeGame`).
+/// - The supplied sequencer commitment fails verification
+///   (`MaldaError::InvalidSequencerCommitment`).
+/// - With dual L1 attestation, Optimism and Base never share an L1 origin within
+///   `DUAL_OPSTACK_L1_ATTESTATION_MAX_ATTEMPTS` attempts (`MaldaError::L1OriginMismatch`).
+///
+/// # Panics
+/// Panics if:
+/// - Invalid chain ID is provided.
+/// - RPC calls fail.
+pub async fn get_proof_data_zkvm_input_with_options(
+    users: Vec<Address>,
+    markets: Vec<Address>,
+    target_chain_ids: Vec<u64>,
+    chain_id: u64,
+    safety: impl Into<BlockSafety>,
+    options: ZkvmInputOptions,
+) -> Result<Vec<u8>, Error> {
+    get_zkvm_input(
+        GuestCalls::ProofData {
+            users,
+            markets,
+            target_chain_ids,
+        },
+        chain_id,
+        safety.into(),
+        options,
+    )
+    .await
+}
+
+/// Prepares input data for the contract call guest for a single chain's view calls.
+///
+/// The input is laid out like `get_proof_data_zkvm_input_with_options`, with the calls in
+/// place of the users, markets and target chains.
+///
+/// # Arguments
+/// * `calls` - View calls to prove, batched into one multicall.
+/// * `chain_id` - Chain ID for the calls.
+/// * `safety` - Block the proof anchors to, see `BlockSafety`. A `ProofLane`, or `true` for
+///   the slow lane, is accepted too.
+/// * `options` - Pre-fetched data to use instead of querying it.
 ///
 /// # Returns
-/// * `Vec<u8>` - Serialized input data for the ZKVM.
+/// * `Result<Vec<u8>, Error>` - Serialized input data for the ZKVM.
+///
+/// # Errors
+/// See `get_proof_data_zkvm_input_with_options`.
 ///
 /// # Panics
 /// Panics if:
 /// - Invalid chain ID is provided.
 /// - RPC calls fail.
-pub async fn get_proof_data_zkvm_input(
-    users: Vec<Address>,
-    markets: Vec<Address>,
-    target_chain_ids: Vec<u64>,
+pub async fn get_contract_call_zkvm_input(
+    calls: Vec<ContractCall>,
     chain_id: u64,
-    l1_inclusion: bool,
-) -> Vec<u8> {
-    let is_sepolia = chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-        || chain_id == BASE_SEPOLIA_CHAIN_ID
-        || chain_id == ETHEREUM_SEPOLIA_CHAIN_ID
-        || chain_id == LINEA_SEPOLIA_CHAIN_ID;
+    safety: impl Into<BlockSafety>,
+    options: ZkvmInputOptions,
+) -> Result<Vec<u8>, Error> {
+    get_zkvm_input(
+        GuestCalls::Contract(calls),
+        chain_id,
+        safety.into(),
+        options,
+    )
+    .await
+}
+
+/// View calls a chain's guest input is built for.
+enum GuestCalls {
+    /// `getProofData` calls of the get-proof-data guest, one target chain per user.
+    ProofData {
+        users: Vec<Address>,
+        markets: Vec<Address>,
+        target_chain_ids: Vec<u64>,
+    },
+    /// Arbitrary view calls of the contract call guest.
+    Contract(Vec<ContractCall>),
+}
+
+/// Builds a single chain's guest input for `calls`, see
+/// `get_proof_data_zkvm_input_with_options`.
+async fn get_zkvm_input(
+    calls: GuestCalls,
+    chain_id: u64,
+    safety: BlockSafety,
+    options: ZkvmInputOptions,
+) -> Result<Vec<u8>, Error> {
+    let l1_inclusion = safety.lane().l1_inclusion();
+    let chain = Chain::from_id(chain_id).expect("invalid chain id");
+    let is_sepolia = chain.is_sepolia();
 
     let rpc_url = match chain_id {
         BASE_CHAIN_ID => rpc_url_base(),
@@ -509,91 +1509,275 @@ pub async fn get_proof_data_zkvm_input(
         _ => panic!("Invalid chain ID"),
     };
 
-    let (block, commitment, block_2, commitment_2) =
-        get_sequencer_commitments_and_blocks(chain_id, rpc_url, is_sepolia, l1_inclusion).await;
-
-    let (l1_block_call_input_1, ethereum_block_1, l1_block_call_input_2, _ethereum_block_2) =
-        get_l1block_call_inputs_and_l1_block_numbers(
+    let start_time = std::time::Instant::now();
+    let mut sequencer_time = Duration::ZERO;
+    let mut l1_block_time = Duration::ZERO;
+
+    // With dual L1 attestation the Optimism and Base heads must reference the same L1 block,
+    // so refetch both commitments until their L1 origins line up.
+    let mut attempts = 0;
+    let (
+        block,
+        commitment,
+        commitment_2,
+        l1_block_call_input_1,
+        ethereum_block_1,
+        l1_block_call_input_2,
+    ) = loop {
+        let (commitments, elapsed) = timed(get_sequencer_commitments_and_blocks(
             chain_id,
+            rpc_url,
             is_sepolia,
             l1_inclusion,
-            block,
-            block_2,
-        )
+            options.dual_opstack_l1_attestation,
+            options.sequencer_commitment.clone(),
+        ))
         .await;
+        sequencer_time += elapsed;
+        let (block, commitment, block_2, commitment_2) = commitments?;
 
-    let (env_input_l1_inclusion, l2_block_number_on_l1) =
-        get_env_input_for_l1_inclusion_and_l2_block_number(
+        let (l1_block_call_inputs, elapsed) = timed(get_l1block_call_inputs_and_l1_block_numbers(
             chain_id,
             is_sepolia,
             l1_inclusion,
-            ethereum_block_1,
-        )
+            block,
+            block_2,
+        ))
         .await;
+        l1_block_time += elapsed;
+        let (l1_block_call_input_1, ethereum_block_1, l1_block_call_input_2, ethereum_block_2) =
+            l1_block_call_inputs?;
+
+        attempts += 1;
+        if ethereum_block_2.is_none() || ethereum_block_1 == ethereum_block_2 {
+            break (
+                block,
+                commitment,
+                commitment_2,
+                l1_block_call_input_1,
+                ethereum_block_1,
+                l1_block_call_input_2,
+            );
+        }
+        if attempts >= DUAL_OPSTACK_L1_ATTESTATION_MAX_ATTEMPTS {
+            return Err(MaldaError::L1OriginMismatch { attempts }.into());
+        }
+        tokio::time::sleep(DUAL_OPSTACK_L1_ATTESTATION_RETRY_INTERVAL).await;
+    };
 
-    let block =
-        if l1_inclusion && (chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID) {
-            l2_block_number_on_l1.unwrap()
-        } else if chain_id == ETHEREUM_CHAIN_ID
-            || chain_id == ETHEREUM_SEPOLIA_CHAIN_ID
-            || (chain_id == OPTIMISM_CHAIN_ID
-                || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-                || chain_id == BASE_CHAIN_ID
-                || chain_id == BASE_SEPOLIA_CHAIN_ID)
-                && l1_inclusion
-        {
-            ethereum_block_1.unwrap()
-        } else {
-            block.unwrap()
-        };
+    // Linea's L1 inclusion env decides which L2 block is queried, so it has to be built first.
+    // For every other chain the queried block is already known and the L1 inclusion env is
+    // built alongside the linking blocks and view call input below.
+    let linea_start_time = std::time::Instant::now();
+    let (linea_env_input_l1_inclusion, block) = if l1_inclusion && chain.is_linea() {
+        let (env_input_l1_inclusion, l2_block_number_on_l1) =
+            get_env_input_for_l1_inclusion_and_l2_block_number(
+                chain_id,
+                is_sepolia,
+                l1_inclusion,
+                ethereum_block_1,
+            )
+            .await?;
+        let l2_block_number_on_l1 = l2_block_number_on_l1
+            .with_context(|| format!("L1 inclusion for chain {} returned no L2 block", chain_id))?;
+        (Some(env_input_l1_inclusion), l2_block_number_on_l1)
+    } else if chain.is_ethereum() || (chain.is_opstack() && l1_inclusion) {
+        let ethereum_block_1 = ethereum_block_1.with_context(|| {
+            format!(
+                "L1 block required for chain {} with l1_inclusion {}",
+                chain_id, l1_inclusion
+            )
+        })?;
+        (None, ethereum_block_1)
+    } else {
+        let block = block.with_context(|| format!("No block to query for chain {}", chain_id))?;
+        (None, block)
+    };
+    let linea_l1_inclusion_time = linea_start_time.elapsed();
 
-    let (chaind_id_linking_blocks, rpc_url_linking_blocks) = if (chain_id == OPTIMISM_CHAIN_ID
-        || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-        || chain_id == BASE_CHAIN_ID
-        || chain_id == BASE_SEPOLIA_CHAIN_ID)
-        && l1_inclusion
-    {
-        if chain_id == OPTIMISM_CHAIN_ID || chain_id == BASE_CHAIN_ID {
-            (ETHEREUM_CHAIN_ID, rpc_url_ethereum())
-        } else {
+    let (chaind_id_linking_blocks, rpc_url_linking_blocks) = if chain.is_opstack() && l1_inclusion {
+        if is_sepolia {
             (ETHEREUM_SEPOLIA_CHAIN_ID, rpc_url_ethereum_sepolia())
+        } else {
+            (ETHEREUM_CHAIN_ID, rpc_url_ethereum())
         }
     } else {
         (chain_id, rpc_url)
     };
 
-    let (linking_blocks, (proof_data_call_input, proof_data_call_input_op)) = tokio::join!(
-        get_linking_blocks(chaind_id_linking_blocks, rpc_url_linking_blocks, block),
-        get_proof_data_call_input(
-            chain_id,
-            rpc_url,
+    // On the safe block the chain's safe block is queried instead of the reorg protected one,
+    // linked to the anchored head by correspondingly more linking blocks.
+    let safe_block = if safety == BlockSafety::Safe {
+        Some(get_safe_block_number(chain_id, rpc_url, block).await?)
+    } else {
+        None
+    };
+    // The Ethereum path anchors to the non-final L1 block seen by OpStack, so an extra buffer
+    // moves the queried block further below it in the same way.
+    let query_block = if options.ethereum_reorg_buffer > 0 && chain.is_ethereum() {
+        Some(buffered_query_block(
             block,
-            users.clone(),
-            markets.clone(),
-            target_chain_ids.clone(),
-            l1_inclusion
-        )
+            chain.reorg_depth(),
+            safe_block,
+            options.ethereum_reorg_buffer,
+        )?)
+    } else {
+        safe_block
+    };
+    let (
+        (env_input_l1_inclusion, l1_inclusion_time),
+        (linking_blocks, linking_blocks_time),
+        (proof_data_call_input, preflight_time),
+    ) = tokio::join!(
+        timed(async {
+            match linea_env_input_l1_inclusion {
+                Some(env_input_l1_inclusion) => Ok((env_input_l1_inclusion, None)),
+                None => {
+                    get_env_input_for_l1_inclusion_and_l2_block_number(
+                        chain_id,
+                        is_sepolia,
+                        l1_inclusion,
+                        ethereum_block_1,
+                    )
+                    .await
+                }
+            }
+        }),
+        timed(async {
+            match query_block {
+                Some(query_block) => {
+                    get_linking_blocks_from(rpc_url_linking_blocks, query_block + 1, block).await
+                }
+                None => {
+                    get_linking_blocks(chaind_id_linking_blocks, rpc_url_linking_blocks, block)
+                        .await
+                }
+            }
+        }),
+        timed(async {
+            let query_block = match query_block {
+                Some(query_block) => query_block,
+                None => {
+                    block
+                        - reorg_protection_depth(chain_id)
+                            .map_err(|err| anyhow::anyhow!("{:#}", err))?
+                }
+            };
+            match &calls {
+                GuestCalls::ProofData {
+                    users,
+                    markets,
+                    target_chain_ids,
+                } => {
+                    get_proof_data_call_input_at(
+                        chain_id,
+                        rpc_url,
+                        query_block,
+                        users.clone(),
+                        markets.clone(),
+                        target_chain_ids
+                            .iter()
+                            .map(|target| vec![*target])
+                            .collect(),
+                        l1_inclusion,
+                    )
+                    .await
+                }
+                GuestCalls::Contract(contract_calls) => {
+                    get_contract_call_input_at(
+                        chain_id,
+                        rpc_url,
+                        query_block,
+                        contract_calls.clone(),
+                        l1_inclusion,
+                    )
+                    .await
+                }
+            }
+        })
+    );
+    info!(
+        chain_id,
+        sequencer_ms = sequencer_time.as_millis() as u64,
+        l1_block_ms = l1_block_time.as_millis() as u64,
+        l1_inclusion_ms = (linea_l1_inclusion_time + l1_inclusion_time).as_millis() as u64,
+        linking_blocks_ms = linking_blocks_time.as_millis() as u64,
+        preflight_ms = preflight_time.as_millis() as u64,
+        total_ms = start_time.elapsed().as_millis() as u64,
+        "Input phase timings"
     );
+    let (env_input_l1_inclusion, _) = env_input_l1_inclusion?;
+    let (proof_data_call_input, proof_data_call_input_op) = proof_data_call_input?;
 
+    // The guests read the calls between the chain ID and the sequencer commitment.
+    let call_words = match &calls {
+        GuestCalls::ProofData {
+            users,
+            markets,
+            target_chain_ids,
+        } => risc0_zkvm::serde::to_vec(&(users, markets, target_chain_ids)),
+        GuestCalls::Contract(contract_calls) => risc0_zkvm::serde::to_vec(contract_calls),
+    }
+    .unwrap();
     let input: Vec<u8> = bytemuck::pod_collect_to_vec(
-        &risc0_zkvm::serde::to_vec(&(
-            &proof_data_call_input,
-            &chain_id,
-            &users,
-            &markets,
-            &target_chain_ids,
-            &commitment,
-            &l1_block_call_input_1,
-            &linking_blocks,
-            &env_input_l1_inclusion,
-            &proof_data_call_input_op,
-            &commitment_2,
-            &l1_block_call_input_2,
-        ))
-        .unwrap(),
+        &[
+            risc0_zkvm::serde::to_vec(&(&proof_data_call_input, &chain_id)).unwrap(),
+            call_words,
+            risc0_zkvm::serde::to_vec(&(
+                &commitment,
+                &l1_block_call_input_1,
+                &linking_blocks,
+                &env_input_l1_inclusion,
+                &proof_data_call_input_op,
+                &commitment_2,
+                &l1_block_call_input_2,
+            ))
+            .unwrap(),
+        ]
+        .concat(),
     );
 
-    input
+    Ok(input)
+}
+
+/// Returns the block queried on the Ethereum path with a reorg buffer.
+///
+/// The buffer moves the query below the safe block, or else the reorg protected block, so
+/// `block - query_block` linking blocks connect it to the anchored `block`.
+///
+/// # Arguments
+/// * `block` - The anchored block number.
+/// * `reorg_depth` - Reorg protection depth of the chain.
+/// * `safe_block` - The safe block to query instead of the reorg protected one, if any.
+/// * `buffer` - Extra blocks to query below it.
+///
+/// # Returns
+/// * `Result<u64, Error>` - The block number to query.
+///
+/// # Errors
+/// Returns an error if the reorg protection depth and buffer reach below the genesis block.
+fn buffered_query_block(
+    block: u64,
+    reorg_depth: u64,
+    safe_block: Option<u64>,
+    buffer: u64,
+) -> Result<u64, Error> {
+    safe_block
+        .or_else(|| block.checked_sub(reorg_depth))
+        .and_then(|reorg_protected| reorg_protected.checked_sub(buffer))
+        .with_context(|| {
+            format!(
+                "Reorg buffer of {} blocks reaches below genesis from block {}",
+                buffer, block
+            )
+        })
+}
+
+/// Awaits a future and returns its output together with the time it took.
+async fn timed<F: std::future::Future>(future: F) -> (F::Output, Duration) {
+    let start_time = std::time::Instant::now();
+    let output = future.await;
+    (output, start_time.elapsed())
 }
 
 /// Returns the environment input for L1 inclusion and the L2 block number for a given chain.
@@ -605,7 +1789,10 @@ pub async fn get_proof_data_zkvm_input(
 /// * `ethereum_block` - The Ethereum block number (optional).
 ///
 /// # Returns
-/// * `(Option<EvmInput<RlpHeader<Header>>>, Option<u64>)` - The environment input and L2 block number, if available.
+/// * `Result<(Option<EvmInput<RlpHeader<Header>>>, Option<u64>), Error>` - The environment input and L2 block number, if available.
+///
+/// # Errors
+/// Returns `MaldaError::NoMatureDisputeGame` if an OpStack chain has no mature finalized dispute game.
 ///
 /// # Panics
 /// Panics if:
@@ -615,34 +1802,28 @@ pub async fn get_env_input_for_l1_inclusion_and_l2_block_number(
     is_sepolia: bool,
     l1_inclusion: bool,
     ethereum_block: Option<u64>,
-) -> (Option<EvmInput<RlpHeader<Header>>>, Option<u64>) {
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<u64>), Error> {
     if !l1_inclusion {
-        (None, None)
+        Ok((None, None))
     } else {
         let l1_rpc_url = match is_sepolia {
             true => rpc_url_ethereum_sepolia(),
             false => rpc_url_ethereum(),
         };
-        let l1_block = if chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID {
-            ethereum_block.unwrap()
+        let ethereum_block = ethereum_block.with_context(|| {
+            format!("L1 block required for chain {} with l1_inclusion", chain_id)
+        })?;
+        let chain = Chain::from_id(chain_id).expect("invalid chain id");
+        let l1_block = if chain.is_linea() {
+            ethereum_block
         } else {
-            if is_sepolia {
-                ethereum_block.unwrap() - REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA
-            } else if !is_sepolia {
-                ethereum_block.unwrap() - REORG_PROTECTION_DEPTH_ETHEREUM
-            } else {
-                panic!("Invalid chain ID");
-            }
+            ethereum_block - chain.l1().reorg_depth()
         };
 
-        if chain_id == OPTIMISM_CHAIN_ID
-            || chain_id == BASE_CHAIN_ID
-            || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-            || chain_id == BASE_SEPOLIA_CHAIN_ID
-        {
+        if chain.is_opstack() {
             get_env_input_for_opstack_dispute_game(chain_id, l1_block).await
-        } else if chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID {
-            get_env_input_for_linea_l1_call(chain_id, l1_rpc_url, l1_block).await
+        } else if chain.is_linea() {
+            Ok(get_env_input_for_linea_l1_call(chain_id, l1_rpc_url, l1_block).await)
         } else {
             panic!(
                 "L1 Inclusion only supported for Optimism, Base, Linea and their Sepolia variants"
@@ -651,6 +1832,30 @@ pub async fn get_env_input_for_l1_inclusion_and_l2_block_number(
     }
 }
 
+/// Returns the last Linea L2 block posted to L1.
+///
+/// Queries the L1 message service at the latest L1 block, e.g. to check how far the slow
+/// lane lags the L2 head.
+///
+/// # Arguments
+/// * `chain_id` - The Linea chain ID (mainnet or Sepolia).
+/// * `l1_rpc_url` - The L1 RPC URL.
+///
+/// # Returns
+/// * `Result<u64, Error>` - The last L2 block number posted to L1.
+///
+/// # Errors
+/// Returns an error if the RPC URL can't be parsed, the L1 environment can't be built or
+/// the call fails.
+///
+/// # Panics
+/// Panics if an invalid chain ID is provided.
+pub async fn get_linea_l1_posted_block(chain_id: u64, l1_rpc_url: &str) -> Result<u64, Error> {
+    let (_, l2_block_number) =
+        preflight_linea_l1_posted_block(chain_id, l1_rpc_url, BlockNumberOrTag::Latest).await?;
+    Ok(l2_block_number)
+}
+
 /// Returns the environment input and L2 block number for Linea L1 call.
 ///
 /// # Arguments
@@ -664,44 +1869,67 @@ pub async fn get_env_input_for_l1_inclusion_and_l2_block_number(
 /// # Panics
 /// Panics if:
 /// - Invalid chain ID is provided.
+/// - The L1 environment can't be built or the call fails.
 pub async fn get_env_input_for_linea_l1_call(
     chain_id: u64,
     l1_rpc_url: &str,
     l1_block: u64,
 ) -> (Option<EvmInput<RlpHeader<Header>>>, Option<u64>) {
-    let message_service_address = match chain_id {
-        LINEA_CHAIN_ID => L1_MESSAGE_SERVICE_LINEA,
-        LINEA_SEPOLIA_CHAIN_ID => L1_MESSAGE_SERVICE_LINEA_SEPOLIA,
-        _ => panic!("Invalid chain ID"),
-    };
+    let (input, l2_block_number) =
+        preflight_linea_l1_posted_block(chain_id, l1_rpc_url, BlockNumberOrTag::Number(l1_block))
+            .await
+            .expect("Failed to read the last L2 block posted to L1");
+
+    (Some(input), Some(l2_block_number))
+}
+
+/// Preflights the L1 message service call for the last Linea L2 block posted to L1.
+///
+/// # Arguments
+/// * `chain_id` - The Linea chain ID (mainnet or Sepolia).
+/// * `l1_rpc_url` - The L1 RPC URL.
+/// * `block` - The L1 block to call at.
+///
+/// # Returns
+/// * `Result<(EvmInput<RlpHeader<Header>>, u64), Error>` - The L1 environment input holding
+///   the call and the last L2 block number posted to L1.
+///
+/// # Errors
+/// Returns an error if the RPC URL can't be parsed, the L1 environment can't be built, the
+/// call fails or the environment can't be converted to an input.
+///
+/// # Panics
+/// Panics if an invalid chain ID is provided.
+async fn preflight_linea_l1_posted_block(
+    chain_id: u64,
+    l1_rpc_url: &str,
+    block: BlockNumberOrTag,
+) -> Result<(EvmInput<RlpHeader<Header>>, u64), Error> {
+    let message_service_address = linea_l1_message_service(chain_id);
 
     let mut env = EthEvmEnv::builder()
-        .rpc(Url::parse(l1_rpc_url).expect("Failed to parse RPC URL"))
-        .block_number_or_tag(BlockNumberOrTag::Number(l1_block))
+        .rpc(Url::parse(l1_rpc_url).context("Failed to parse RPC URL")?)
+        .block_number_or_tag(block)
         .build()
         .await
-        .expect("Failed to build EVM environment");
-
-    // Make single multicall
-    let current_l2_block_number_call = IL1MessageService::currentL2BlockNumberCall {};
+        .context("Failed to build EVM environment")?;
 
     let mut contract = Contract::preflight(message_service_address, &mut env);
     let returns = contract
-        .call_builder(&current_l2_block_number_call)
+        .call_builder(&IL1MessageService::currentL2BlockNumberCall {})
         .call()
         .await
-        .expect("Failed to execute current l2 block number call");
+        .context("Failed to execute current l2 block number call")?;
 
-    let l2_block_number: u64 = U64::from(returns._0).try_into().unwrap();
+    let l2_block_number = u64::try_from(returns._0)
+        .map_err(|_| anyhow::anyhow!("Last L2 block number posted to L1 exceeds u64"))?;
 
-    (
-        Some(
-            env.into_input()
-                .await
-                .expect("Failed to convert environment to input"),
-        ),
-        Some(l2_block_number),
-    )
+    let input = env
+        .into_input()
+        .await
+        .context("Failed to convert environment to input")?;
+
+    Ok((input, l2_block_number))
 }
 
 /// Returns the environment input for OpStack dispute game and a dummy L2 block number.
@@ -711,15 +1939,24 @@ pub async fn get_env_input_for_linea_l1_call(
 /// * `l1_block` - The L1 block number.
 ///
 /// # Returns
-/// * `(Option<EvmInput<RlpHeader<Header>>>, Option<u64>)` - The environment input and a dummy L2 block number.
+/// * `Result<(Option<EvmInput<RlpHeader<Header>>>, Option<u64>), Error>` - The environment input and a dummy L2 block number.
+///
+/// # Errors
+/// Returns `MaldaError::NoMatureDisputeGame` if:
+/// - No finalized dispute game has passed the proof maturity delay at `l1_block`, checked
+///   with `has_mature_dispute_game` when the dispute game environment can't be built.
+/// - The selected finalized game hasn't passed the proof maturity delay at `l1_block`.
+///
+/// Returns any other RPC or environment error unchanged.
 ///
 /// # Panics
 /// Panics if:
 /// - Invalid chain ID is provided.
+/// - The finalized game fails the type, status, blacklist or root claim checks.
 pub async fn get_env_input_for_opstack_dispute_game(
     chain_id: u64,
     l1_block: u64,
-) -> (Option<EvmInput<RlpHeader<Header>>>, Option<u64>) {
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<u64>), Error> {
     let (l1_rpc_url, optimism_portal, l2_rpc_url) = match chain_id {
         OPTIMISM_CHAIN_ID => (rpc_url_ethereum(), OPTIMISM_PORTAL, rpc_url_optimism()),
         OPTIMISM_SEPOLIA_CHAIN_ID => (
@@ -735,38 +1972,43 @@ pub async fn get_env_input_for_opstack_dispute_game(
         ),
         _ => panic!("Invalid chain ID"),
     };
+    let l1_url = Url::parse(l1_rpc_url).context("Failed to parse L1 RPC URL")?;
+    let l2_url = Url::parse(l2_rpc_url).context("Failed to parse L2 RPC URL")?;
 
     let mut env = EthEvmEnv::builder()
-        .rpc(Url::parse(l1_rpc_url).expect("Failed to parse RPC URL"))
+        .rpc(l1_url.clone())
         .block_number_or_tag(BlockNumberOrTag::Number(l1_block))
         .build()
         .await
-        .expect("Failed to build EVM environment");
+        .context("Failed to build EVM environment")?;
     let builder = OpEvmEnv::builder()
-        .dispute_game_from_rpc(
-            optimism_portal,
-            Url::parse(l1_rpc_url).expect("Failed to parse RPC URL"),
-        )
+        .dispute_game_from_rpc(optimism_portal, l1_url.clone())
         .game_index(DisputeGameIndex::Finalized);
-    let mut op_env = builder
-        .rpc(Url::parse(l2_rpc_url).expect("Failed to parse RPC URL"))
-        .build()
-        .await
-        .expect("Failed to build OP-EVM environment");
+    // Building fails when the factory has no finalized game yet, e.g. right after a deployment,
+    // but also on RPC errors, so only the former is reported as a missing game.
+    let mut op_env = match builder.rpc(l2_url).build().await {
+        Ok(op_env) => op_env,
+        Err(err) => {
+            if !has_mature_dispute_game(chain_id, l1_url, optimism_portal, l1_block).await? {
+                return Err(MaldaError::NoMatureDisputeGame { chain_id }.into());
+            }
+            return Err(err);
+        }
+    };
 
     // This is just an arbitrary simple call needed in order to do into_env to get the game_index
-    let mut contract = Contract::preflight(L1_BLOCK_ADDRESS_OPSTACK, &mut op_env);
+    let mut contract = Contract::preflight(l1_block_address(chain_id), &mut op_env);
     let block_hash_call = IL1Block::hashCall {};
     let _returns = contract
         .call_builder(&block_hash_call)
         .call()
         .await
-        .expect("Failed to execute factory call");
+        .context("Failed to execute L1 block hash call")?;
 
     let input = op_env
         .into_input()
         .await
-        .expect("Failed to convert environment to input");
+        .context("Failed to convert environment to input")?;
     let op_env_commitment = input.clone().into_env().into_commitment();
 
     let (game_index, _version) = op_env_commitment.decode_id();
@@ -790,7 +2032,7 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&factory_call)
         .call()
         .await
-        .expect("Failed to execute factory call");
+        .context("Failed to execute factory call")?;
     let factory_address = returns._0;
 
     let game_call = IDisputeGameFactory::gameAtIndexCall { index: game_index };
@@ -800,10 +2042,13 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&game_call)
         .call()
         .await
-        .expect("Failed to execute game at index call");
+        .context("Failed to execute game at index call")?;
 
     let game_type = returns._0;
-    assert_eq!(game_type, U256::from(0), "game type not respected game");
+    assert!(
+        allowed_game_types(chain_id).contains(&game_type),
+        "game type not respected game"
+    );
 
     let created_at = returns._1;
     let game_address = returns._2;
@@ -815,7 +2060,7 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&respected_game_type_updated_at_call)
         .call()
         .await
-        .expect("Failed to execute respected game type updated at call");
+        .context("Failed to execute respected game type updated at call")?;
     assert!(
         created_at >= returns._0,
         "game created before respected game type update"
@@ -830,7 +2075,7 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&status_call)
         .call()
         .await
-        .expect("Failed to execute status call");
+        .context("Failed to execute status call")?;
     assert_eq!(
         returns._0,
         GameStatus::DEFENDER_WINS,
@@ -844,7 +2089,7 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&blacklist_call)
         .call()
         .await
-        .expect("Failed to execute blacklist call");
+        .context("Failed to execute blacklist call")?;
     assert!(!returns._0, "game is blacklisted");
 
     // Check game resolution time
@@ -854,7 +2099,7 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&resolved_at_call)
         .call()
         .await
-        .expect("Failed to execute resolved at call");
+        .context("Failed to execute resolved at call")?;
     let resolved_at = returns._0;
 
     let mut contract = Contract::preflight(portal_adress, &mut env);
@@ -863,15 +2108,16 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&proof_maturity_delay_call)
         .call()
         .await
-        .expect("Failed to execute proof maturity delay call");
+        .context("Failed to execute proof maturity delay call")?;
     let proof_maturity_delay = returns._0;
 
+    // The game may have resolved after `l1_block`, so compare without subtracting timestamps.
     let current_timestamp = env.header().inner().inner().timestamp;
-    assert!(
-        U256::from(current_timestamp) - U256::from(resolved_at)
-            > proof_maturity_delay - U256::from(300),
-        "insufficient time passed since game resolution"
-    );
+    if U256::from(current_timestamp)
+        <= U256::from(resolved_at) + proof_maturity_delay.saturating_sub(U256::from(300))
+    {
+        return Err(MaldaError::NoMatureDisputeGame { chain_id }.into());
+    }
 
     // Finally verify root claim matches
     let mut contract = Contract::preflight(game_address, &mut env);
@@ -880,112 +2126,352 @@ pub async fn get_env_input_for_opstack_dispute_game(
         .call_builder(&root_claim_call)
         .call()
         .await
-        .expect("Failed to execute root claim call");
+        .context("Failed to execute root claim call")?;
 
     assert_eq!(returns._0, root_claim, "root claim not respected");
 
-    (
-        Some(
-            env.into_input()
-                .await
-                .expect("Failed to convert environment to input"),
-        ),
-        // irrelevant for l1 inclusion on opstack
-        Some(1),
-    )
+    info!(
+        chain_id,
+        l1_block,
+        %game_index,
+        %game_address,
+        %created_at,
+        %resolved_at,
+        %root_claim,
+        "Selected dispute game for L1 inclusion"
+    );
+
+    Ok((
+        Some(
+            env.into_input()
+                .await
+                .context("Failed to convert environment to input")?,
+        ),
+        // irrelevant for l1 inclusion on opstack
+        Some(1),
+    ))
+}
+
+/// Checks whether the dispute game factory holds a finalized game past the proof maturity
+/// delay at `l1_block`.
+///
+/// A game can't resolve before it's created, so only games created at least the maturity
+/// delay before `l1_block` qualify. The newest of those is found by binary search over the
+/// factory, and up to `DISPUTE_GAME_MATURITY_SEARCH_DEPTH` games are checked from there
+/// backwards for a respected type, `DEFENDER_WINS` status and a mature resolution.
+///
+/// # Arguments
+/// * `chain_id` - The OpStack chain ID.
+/// * `l1_rpc_url` - The L1 RPC URL.
+/// * `portal` - The chain's `OptimismPortal` address.
+/// * `l1_block` - The L1 block number to check at.
+///
+/// # Returns
+/// * `Result<bool, Error>` - Whether a mature finalized game exists.
+///
+/// # Errors
+/// Returns an error if an RPC call fails.
+async fn has_mature_dispute_game(
+    chain_id: u64,
+    l1_rpc_url: Url,
+    portal: Address,
+    l1_block: u64,
+) -> Result<bool, Error> {
+    let mut env = EthEvmEnv::builder()
+        .rpc(l1_rpc_url)
+        .block_number_or_tag(BlockNumberOrTag::Number(l1_block))
+        .build()
+        .await
+        .context("Failed to build EVM environment")?;
+    let timestamp = U256::from(env.header().inner().inner().timestamp);
+
+    let factory_address = Contract::preflight(portal, &mut env)
+        .call_builder(&IOptimismPortal::disputeGameFactoryCall {})
+        .call()
+        .await
+        .context("Failed to execute factory call")?
+        ._0;
+    let maturity_delay = Contract::preflight(portal, &mut env)
+        .call_builder(&IOptimismPortal::proofMaturityDelaySecondsCall {})
+        .call()
+        .await
+        .context("Failed to execute proof maturity delay call")?
+        ._0
+        .saturating_sub(U256::from(300));
+    let game_count = Contract::preflight(factory_address, &mut env)
+        .call_builder(&IDisputeGameFactory::gameCountCall {})
+        .call()
+        .await
+        .context("Failed to execute game count call")?
+        ._0
+        .to::<u64>();
+
+    // Find the first game created too late to be mature at `l1_block`.
+    let (mut low, mut high) = (0, game_count);
+    while low < high {
+        let index = low + (high - low) / 2;
+        let created_at = Contract::preflight(factory_address, &mut env)
+            .call_builder(&IDisputeGameFactory::gameAtIndexCall {
+                index: U256::from(index),
+            })
+            .call()
+            .await
+            .context("Failed to execute game at index call")?
+            ._1;
+        if created_at + maturity_delay < timestamp {
+            low = index + 1;
+        } else {
+            high = index;
+        }
+    }
+
+    for index in (low.saturating_sub(DISPUTE_GAME_MATURITY_SEARCH_DEPTH)..low).rev() {
+        let game = Contract::preflight(factory_address, &mut env)
+            .call_builder(&IDisputeGameFactory::gameAtIndexCall {
+                index: U256::from(index),
+            })
+            .call()
+            .await
+            .context("Failed to execute game at index call")?;
+        if !allowed_game_types(chain_id).contains(&game._0) {
+            continue;
+        }
+        let status = Contract::preflight(game._2, &mut env)
+            .call_builder(&IDisputeGame::statusCall {})
+            .call()
+            .await
+            .context("Failed to execute status call")?
+            ._0;
+        if status != GameStatus::DEFENDER_WINS {
+            continue;
+        }
+        let resolved_at = Contract::preflight(game._2, &mut env)
+            .call_builder(&IDisputeGame::resolvedAtCall {})
+            .call()
+            .await
+            .context("Failed to execute resolved at call")?
+            ._0;
+        if U256::from(resolved_at) + maturity_delay < timestamp {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns L1 block call inputs and L1 block numbers for a given chain.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to query.
+/// * `is_sepolia` - Whether the chain is a Sepolia testnet variant.
+/// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `block` - The block number (optional).
+/// * `block_2` - The Base block number for dual L1 attestation (optional).
+///
+/// # Returns
+/// * Tuple of optional L1 block call inputs and block numbers.
+///
+/// # Errors
+/// Returns an error if the OpStack block number is not provided when the L1 block is required.
+///
+/// # Panics
+/// Panics if RPC calls fail.
+pub async fn get_l1block_call_inputs_and_l1_block_numbers(
+    chain_id: u64,
+    is_sepolia: bool,
+    l1_inclusion: bool,
+    block: Option<u64>,
+    block_2: Option<u64>,
+) -> Result<
+    (
+        Option<EvmInput<RlpHeader<Header>>>,
+        Option<u64>,
+        Option<EvmInput<RlpHeader<Header>>>,
+        Option<u64>,
+    ),
+    Error,
+> {
+    if Chain::from_id(chain_id).is_some_and(|chain| chain.is_ethereum()) || l1_inclusion {
+        let block = block.with_context(|| {
+            format!(
+                "L1 block required for chain {} with l1_inclusion {}",
+                chain_id, l1_inclusion
+            )
+        })?;
+        let (chain_id_1, chain_id_2) = match is_sepolia {
+            true => (OPTIMISM_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID),
+            false => (OPTIMISM_CHAIN_ID, BASE_CHAIN_ID),
+        };
+        match block_2 {
+            Some(block_2) => {
+                let (
+                    (l1_block_call_input_1, ethereum_block_1),
+                    (l1_block_call_input_2, ethereum_block_2),
+                ) = tokio::join!(
+                    get_l1block_call_input(BlockNumberOrTag::Number(block), chain_id_1),
+                    get_l1block_call_input(BlockNumberOrTag::Number(block_2), chain_id_2)
+                );
+                Ok((
+                    Some(l1_block_call_input_1),
+                    Some(ethereum_block_1),
+                    Some(l1_block_call_input_2),
+                    Some(ethereum_block_2),
+                ))
+            }
+            None => {
+                let (l1_block_call_input_1, ethereum_block_1) =
+                    get_l1block_call_input(BlockNumberOrTag::Number(block), chain_id_1).await;
+                Ok((
+                    Some(l1_block_call_input_1),
+                    Some(ethereum_block_1),
+                    None,
+                    None,
+                ))
+            }
+        }
+    } else {
+        Ok((None, None, None, None))
+    }
+}
+
+/// Expands per-user target chain lists into one `(user, market, target_chain_id)` row per target.
+///
+/// The rows are returned in the order the guest commits them to the journal, so the
+/// flattened vectors can be passed directly to `get_proof_data_zkvm_input`.
+///
+/// # Arguments
+/// * `users` - Vector of user addresses.
+/// * `markets` - Vector of market contract addresses, one per user.
+/// * `target_chain_ids` - Target chain IDs to query, one vector per user.
+///
+/// # Returns
+/// * `(Vec<Address>, Vec<Address>, Vec<u64>)` - Flattened users, markets and target chain IDs.
+///
+/// # Panics
+/// Panics if the input vector lengths don't match.
+pub fn fan_out_target_chains(
+    users: Vec<Address>,
+    markets: Vec<Address>,
+    target_chain_ids: Vec<Vec<u64>>,
+) -> (Vec<Address>, Vec<Address>, Vec<u64>) {
+    assert_eq!(
+        users.len(),
+        markets.len(),
+        "Users and markets array lengths must match"
+    );
+    assert_eq!(
+        users.len(),
+        target_chain_ids.len(),
+        "Users and target_chain_ids array lengths must match"
+    );
+
+    let total = target_chain_ids.iter().map(|targets| targets.len()).sum();
+    let mut flat_users = Vec::with_capacity(total);
+    let mut flat_markets = Vec::with_capacity(total);
+    let mut flat_target_chain_ids = Vec::with_capacity(total);
+
+    for ((user, market), targets) in users
+        .into_iter()
+        .zip(markets.into_iter())
+        .zip(target_chain_ids.into_iter())
+    {
+        for target_chain_id in targets {
+            flat_users.push(user);
+            flat_markets.push(market);
+            flat_target_chain_ids.push(target_chain_id);
+        }
+    }
+
+    (flat_users, flat_markets, flat_target_chain_ids)
 }
 
-/// Returns L1 block call inputs and L1 block numbers for a given chain.
+/// Prepares multicall input for batch proof data checking.
 ///
 /// # Arguments
-/// * `chain_id` - The chain ID to query.
-/// * `is_sepolia` - Whether the chain is a Sepolia testnet variant.
-/// * `l1_inclusion` - Whether to include L1 data in the proof.
-/// * `block` - The block number (optional).
-/// * `_block_2` - The second block number (optional, unused).
+/// * `chain_id` - Chain ID for the queries.
+/// * `chain_url` - RPC URL for the chain.
+/// * `block` - Block number to query at.
+/// * `users` - Vector of user addresses.
+/// * `markets` - Vector of market contract addresses.
+/// * `target_chain_ids` - Target chain IDs to query, one vector per user. Each target
+///   emits its own `Call3` against the same market.
+/// * `validate_l1_inclusion` - Whether to validate L1 inclusion for OpStack chains.
 ///
 /// # Returns
-/// * Tuple of optional L1 block call inputs and block numbers.
+/// * `Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error>` - Formatted EVM
+///   input for the multicall and optional OpEvmInput.
+///
+/// # Errors
+/// Returns an error if:
+/// - The chain is not supported (see `reorg_protection_depth`).
+/// - Building or preflighting the environment still fails after `PREFLIGHT_MAX_ATTEMPTS`
+///   attempts (`MaldaError::PreflightFailed`, with the last failure as its source).
 ///
 /// # Panics
 /// Panics if:
-/// - Block number is not provided when required.
-pub async fn get_l1block_call_inputs_and_l1_block_numbers(
+/// - Invalid chain ID is provided.
+/// - An RPC URL cannot be parsed.
+pub async fn get_proof_data_call_input(
     chain_id: u64,
-    is_sepolia: bool,
-    l1_inclusion: bool,
-    block: Option<u64>,
-    _block_2: Option<u64>,
-) -> (
-    Option<EvmInput<RlpHeader<Header>>>,
-    Option<u64>,
-    Option<EvmInput<RlpHeader<Header>>>,
-    Option<u64>,
-) {
-    if chain_id == ETHEREUM_CHAIN_ID || chain_id == ETHEREUM_SEPOLIA_CHAIN_ID || l1_inclusion {
-        let (chain_id_1, _chain_id_2) = match is_sepolia {
-            true => (OPTIMISM_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID),
-            false => (OPTIMISM_CHAIN_ID, BASE_CHAIN_ID),
-        };
-        let (l1_block_call_input_1, ethereum_block_1) =
-            get_l1block_call_input(BlockNumberOrTag::Number(block.unwrap()), chain_id_1).await;
-        // let (l1_block_call_input_2, ethereum_block_2) =
-        //     get_l1block_call_input(BlockNumberOrTag::Number(block_2.unwrap()), chain_id_2).await;
-
-        (
-            Some(l1_block_call_input_1),
-            Some(ethereum_block_1),
-            None::<EvmInput<RlpHeader<Header>>>,
-            None::<u64>,
-        )
-        // (Some(l1_block_call_input_1), Some(ethereum_block_1), Some(l1_block_call_input_2), Some(ethereum_block_2))
-    } else {
-        (None, None, None, None)
-    }
+    chain_url: &str,
+    block: u64,
+    users: Vec<Address>,
+    markets: Vec<Address>,
+    target_chain_ids: Vec<Vec<u64>>,
+    validate_l1_inclusion: bool,
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error> {
+    let reorg_protection_depth =
+        reorg_protection_depth(chain_id).map_err(|err| anyhow::anyhow!("{:#}", err))?;
+
+    get_proof_data_call_input_at(
+        chain_id,
+        chain_url,
+        block - reorg_protection_depth,
+        users,
+        markets,
+        target_chain_ids,
+        validate_l1_inclusion,
+    )
+    .await
 }
 
-/// Prepares multicall input for batch proof data checking.
+/// Prepares multicall input for batch proof data checking at an exact block.
+///
+/// Unlike `get_proof_data_call_input`, the reorg protection depth is not subtracted from
+/// `block_reorg_protected`; the caller links it to the anchored head.
 ///
 /// # Arguments
 /// * `chain_id` - Chain ID for the queries.
 /// * `chain_url` - RPC URL for the chain.
-/// * `block` - Block number to query at.
+/// * `block_reorg_protected` - Block number to query at.
 /// * `users` - Vector of user addresses.
 /// * `markets` - Vector of market contract addresses.
-/// * `target_chain_ids` - Vector of target chain IDs to query.
+/// * `target_chain_ids` - Target chain IDs to query, one vector per user. Each target
+///   emits its own `Call3` against the same market.
 /// * `validate_l1_inclusion` - Whether to validate L1 inclusion for OpStack chains.
 ///
 /// # Returns
-/// * `(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>)` - Formatted EVM input for the multicall and optional OpEvmInput.
+/// * `Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error>` - Formatted EVM
+///   input for the multicall and optional OpEvmInput.
+///
+/// # Errors
+/// Returns `MaldaError::PreflightFailed` if building or preflighting the environment still
+/// fails after `PREFLIGHT_MAX_ATTEMPTS` attempts, with the last failure as its source.
 ///
 /// # Panics
 /// Panics if:
 /// - Invalid chain ID is provided.
-/// - RPC connection fails.
-pub async fn get_proof_data_call_input(
+/// - An RPC URL cannot be parsed.
+pub async fn get_proof_data_call_input_at(
     chain_id: u64,
     chain_url: &str,
-    block: u64,
+    block_reorg_protected: u64,
     users: Vec<Address>,
     markets: Vec<Address>,
-    target_chain_ids: Vec<u64>,
+    target_chain_ids: Vec<Vec<u64>>,
     validate_l1_inclusion: bool,
-) -> (Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>) {
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        SCROLL_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        SCROLL_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
-
-    let block_reorg_protected = block - reorg_protection_depth;
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error> {
+    let (users, markets, target_chain_ids) =
+        fan_out_target_chains(users, markets, target_chain_ids);
 
     // Create array of Call3 structs for each proof data check
     let mut calls = Vec::with_capacity(users.len());
@@ -995,15 +2481,13 @@ pub async fn get_proof_data_call_input(
         .zip(markets.iter())
         .zip(target_chain_ids.iter())
     {
-        // Selector for getProofData(address,uint32)
-        let selector = [0x07, 0xd9, 0x23, 0xe9];
         let user_bytes: [u8; 32] = user.into_word().into();
         // Convert chain_id to 4 bytes
         let chain_id_bytes = (*target_chain_id as u32).to_be_bytes();
 
         // Create calldata by concatenating selector, encoded address, and chain ID
         let mut call_data = Vec::with_capacity(68); // 4 bytes selector + 32 bytes address + 4 bytes chain ID
-        call_data.extend_from_slice(&selector);
+        call_data.extend_from_slice(&GET_PROOF_DATA_SELECTOR);
         call_data.extend_from_slice(&user_bytes);
         call_data.extend_from_slice(&[0u8; 28]); // pad chain id to 32 bytes
         call_data.extend_from_slice(&chain_id_bytes);
@@ -1018,13 +2502,133 @@ pub async fn get_proof_data_call_input(
     // Make single multicall
     let multicall = IMulticall3::aggregate3Call { calls };
 
+    preflight_multicall_with_retries(
+        chain_id,
+        chain_url,
+        block_reorg_protected,
+        &multicall,
+        validate_l1_inclusion,
+    )
+    .await
+}
+
+/// Prepares multicall input for a batch of arbitrary view calls at an exact block.
+///
+/// Like `get_proof_data_call_input_at`, for the contract call guest.
+///
+/// # Arguments
+/// * `chain_id` - Chain ID for the calls.
+/// * `chain_url` - RPC URL for the chain.
+/// * `block_reorg_protected` - Block number to query at.
+/// * `calls` - View calls to batch into the multicall.
+/// * `validate_l1_inclusion` - Whether to validate L1 inclusion for OpStack chains.
+///
+/// # Returns
+/// * `Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error>` - Formatted EVM
+///   input for the multicall and optional OpEvmInput.
+///
+/// # Errors
+/// Returns `MaldaError::PreflightFailed` if building or preflighting the environment still
+/// fails after `PREFLIGHT_MAX_ATTEMPTS` attempts, with the last failure as its source.
+///
+/// # Panics
+/// Panics if:
+/// - Invalid chain ID is provided.
+/// - An RPC URL cannot be parsed.
+pub async fn get_contract_call_input_at(
+    chain_id: u64,
+    chain_url: &str,
+    block_reorg_protected: u64,
+    calls: Vec<ContractCall>,
+    validate_l1_inclusion: bool,
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error> {
+    let multicall = IMulticall3::aggregate3Call {
+        calls: calls.into_iter().map(Call3::from).collect(),
+    };
+
+    preflight_multicall_with_retries(
+        chain_id,
+        chain_url,
+        block_reorg_protected,
+        &multicall,
+        validate_l1_inclusion,
+    )
+    .await
+}
+
+/// Preflights a multicall with `preflight_multicall`, retrying failures with backoff.
+///
+/// Preflight issues real `eth_call`s, so transient RPC failures are retried up to
+/// `PREFLIGHT_MAX_ATTEMPTS` times.
+///
+/// # Errors
+/// Returns `MaldaError::PreflightFailed` with the last failure as its source if every attempt
+/// fails.
+async fn preflight_multicall_with_retries(
+    chain_id: u64,
+    chain_url: &str,
+    block_reorg_protected: u64,
+    multicall: &IMulticall3::aggregate3Call,
+    validate_l1_inclusion: bool,
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error> {
+    let mut attempts = 0;
+    let mut backoff = PREFLIGHT_RETRY_BACKOFF;
+    loop {
+        attempts += 1;
+        match preflight_multicall(
+            chain_id,
+            chain_url,
+            block_reorg_protected,
+            multicall,
+            validate_l1_inclusion,
+        )
+        .await
+        {
+            Ok(input) => return Ok(input),
+            Err(err) if attempts >= PREFLIGHT_MAX_ATTEMPTS => {
+                return Err(err.context(MaldaError::PreflightFailed { chain_id, attempts }));
+            }
+            Err(err) => {
+                info!(
+                    "Preflight attempt {} for chain {} failed: {:#}",
+                    attempts, chain_id, err
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+    }
+}
+
+/// Builds the view call environment of a chain and preflights a multicall on it.
+///
+/// # Arguments
+/// * `chain_id` - Chain ID for the queries.
+/// * `chain_url` - RPC URL for the chain.
+/// * `block_reorg_protected` - Block number to query at, when not anchored to a dispute game.
+/// * `multicall` - The multicall to preflight.
+/// * `validate_l1_inclusion` - Whether to validate L1 inclusion for OpStack chains.
+///
+/// # Returns
+/// * `Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error>` - Formatted EVM
+///   input for the multicall and optional OpEvmInput.
+///
+/// # Errors
+/// Returns an error if building the environment, the multicall or the input conversion fails.
+///
+/// # Panics
+/// Panics if:
+/// - Invalid chain ID is provided.
+/// - An RPC URL cannot be parsed.
+async fn preflight_multicall(
+    chain_id: u64,
+    chain_url: &str,
+    block_reorg_protected: u64,
+    multicall: &IMulticall3::aggregate3Call,
+    validate_l1_inclusion: bool,
+) -> Result<(Option<EvmInput<RlpHeader<Header>>>, Option<OpEvmInput>), Error> {
     // Use separate code paths for each environment type
-    if (chain_id == OPTIMISM_CHAIN_ID
-        || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-        || chain_id == BASE_CHAIN_ID
-        || chain_id == BASE_SEPOLIA_CHAIN_ID)
-        && validate_l1_inclusion
-    {
+    if Chain::from_id(chain_id).is_some_and(|chain| chain.is_opstack()) && validate_l1_inclusion {
         // Build an environment based on the state of the latest finalized fault dispute game
         let (l1_rpc_url, optimism_portal) = match chain_id {
             OPTIMISM_CHAIN_ID => (rpc_url_ethereum(), OPTIMISM_PORTAL),
@@ -1043,50 +2647,50 @@ pub async fn get_proof_data_call_input(
             .rpc(Url::parse(chain_url).expect("Failed to parse RPC URL"))
             .build()
             .await
-            .expect("Failed to build OP-EVM environment");
+            .context("Failed to build OP-EVM environment")?;
 
         let mut contract = Contract::preflight(MULTICALL, &mut env);
         let _returns = contract
-            .call_builder(&multicall)
+            .call_builder(multicall)
             // .gas_price(U256::from(gas_price))
             // .from(Address::ZERO)
             .call()
             .await
-            .expect("Failed to execute multicall");
+            .context("Failed to execute multicall")?;
 
-        (
+        Ok((
             None,
             Some(
                 env.into_input()
                     .await
-                    .expect("Failed to convert environment to input"),
+                    .context("Failed to convert environment to input")?,
             ),
-        )
+        ))
     } else {
         let mut env = EthEvmEnv::builder()
             .rpc(Url::parse(chain_url).expect("Failed to parse RPC URL"))
             .block_number_or_tag(BlockNumberOrTag::Number(block_reorg_protected))
             .build()
             .await
-            .expect("Failed to build EVM environment");
+            .context("Failed to build EVM environment")?;
 
         let mut contract = Contract::preflight(MULTICALL, &mut env);
         let _returns = contract
-            .call_builder(&multicall)
+            .call_builder(multicall)
             // .gas_price(U256::from(gas_price))
             // .from(Address::ZERO)
             .call()
             .await
-            .expect("Failed to execute multicall");
+            .context("Failed to execute multicall")?;
 
-        (
+        Ok((
             Some(
                 env.into_input()
                     .await
-                    .expect("Failed to convert environment to input"),
+                    .context("Failed to convert environment to input")?,
             ),
             None,
-        )
+        ))
     }
 }
 
@@ -1097,10 +2701,17 @@ pub async fn get_proof_data_call_input(
 /// * `rpc_url` - The RPC URL for the chain.
 /// * `is_sepolia` - Whether the chain is a Sepolia testnet variant.
 /// * `l1_inclusion` - Whether to include L1 data in the proof.
+/// * `dual_l1_attestation` - Whether to also fetch the Base commitment for dual L1 attestation.
+/// * `sequencer_commitment` - Pre-fetched commitment to verify and use instead of fetching the
+///   first commitment.
 ///
 /// # Returns
-/// * `(Option<u64>, Option<SequencerCommitment>, Option<u64>, Option<SequencerCommitment>)` -
-///   Tuple of (block, commitment, block_2, commitment_2), where the second pair is only relevant for some Sepolia/mainnet cases.
+/// * `Result<(Option<u64>, Option<SequencerCommitment>, Option<u64>, Option<SequencerCommitment>), Error>` -
+///   Tuple of (block, commitment, block_2, commitment_2), where the second pair is the Base
+///   commitment used for dual L1 attestation.
+///
+/// # Errors
+/// Returns `MaldaError::InvalidSequencerCommitment` if the supplied commitment fails verification.
 ///
 /// # Panics
 /// Panics if:
@@ -1111,50 +2722,53 @@ pub async fn get_sequencer_commitments_and_blocks(
     rpc_url: &str,
     is_sepolia: bool,
     l1_inclusion: bool,
-) -> (
-    Option<u64>,
-    Option<SequencerCommitment>,
-    Option<u64>,
-    Option<SequencerCommitment>,
-) {
-    if chain_id == OPTIMISM_CHAIN_ID
-        || chain_id == BASE_CHAIN_ID
-        || chain_id == ETHEREUM_CHAIN_ID
-        || chain_id == OPTIMISM_CHAIN_ID
-        || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-        || chain_id == BASE_SEPOLIA_CHAIN_ID
-        || chain_id == ETHEREUM_SEPOLIA_CHAIN_ID
-        || (chain_id == LINEA_CHAIN_ID && l1_inclusion)
-        || (chain_id == LINEA_SEPOLIA_CHAIN_ID && l1_inclusion)
-    {
-        if !l1_inclusion
-            && (chain_id == OPTIMISM_CHAIN_ID
-                || chain_id == BASE_CHAIN_ID
-                || chain_id == OPTIMISM_SEPOLIA_CHAIN_ID
-                || chain_id == BASE_SEPOLIA_CHAIN_ID)
-        {
-            let (commitment, block) = get_current_sequencer_commitment(chain_id).await;
-            (
+    dual_l1_attestation: bool,
+    sequencer_commitment: Option<SequencerCommitment>,
+) -> Result<
+    (
+        Option<u64>,
+        Option<SequencerCommitment>,
+        Option<u64>,
+        Option<SequencerCommitment>,
+    ),
+    Error,
+> {
+    let chain = Chain::from_id(chain_id).expect("invalid chain id");
+    if chain.is_opstack() || chain.is_ethereum() || (chain.is_linea() && l1_inclusion) {
+        if !l1_inclusion && chain.is_opstack() {
+            let (commitment, block) =
+                get_or_verify_sequencer_commitment(chain_id, sequencer_commitment).await?;
+            Ok((
                 Some(block),
                 Some(commitment),
                 None::<u64>,
                 None::<SequencerCommitment>,
-            )
-        } else if is_sepolia {
-            let (commitment, block) =
-                get_current_sequencer_commitment(OPTIMISM_SEPOLIA_CHAIN_ID).await;
-            // let (commitment_2, block_2) = get_current_sequencer_commitment(BASE_SEPOLIA_CHAIN_ID).await;
-            (Some(block), Some(commitment), None, None)
-            // (Some(block), Some(commitment), Some(block_2), Some(commitment_2))
-        } else if !is_sepolia {
-            let (commitment, block) = get_current_sequencer_commitment(OPTIMISM_CHAIN_ID).await;
-            // let (commitment_2, block_2) = get_current_sequencer_commitment(BASE_CHAIN_ID).await;
-            (Some(block), Some(commitment), None, None)
-            // (Some(block), Some(commitment), Some(block_2), Some(commitment_2))
+            ))
         } else {
-            panic!("Invalid chain ID");
+            let (chain_id_1, chain_id_2) = match is_sepolia {
+                true => (OPTIMISM_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID),
+                false => (OPTIMISM_CHAIN_ID, BASE_CHAIN_ID),
+            };
+            if dual_l1_attestation {
+                let (commitment, commitment_2) = tokio::join!(
+                    get_or_verify_sequencer_commitment(chain_id_1, sequencer_commitment),
+                    get_current_sequencer_commitment(chain_id_2)
+                );
+                let (commitment, block) = commitment?;
+                let (commitment_2, block_2) = commitment_2?;
+                Ok((
+                    Some(block),
+                    Some(commitment),
+                    Some(block_2),
+                    Some(commitment_2),
+                ))
+            } else {
+                let (commitment, block) =
+                    get_or_verify_sequencer_commitment(chain_id_1, sequencer_commitment).await?;
+                Ok((Some(block), Some(commitment), None, None))
+            }
         }
-    } else if chain_id == LINEA_CHAIN_ID || chain_id == LINEA_SEPOLIA_CHAIN_ID {
+    } else if chain.is_linea() {
         let block = EthEvmEnv::builder()
             .rpc(Url::parse(rpc_url).unwrap())
             .block_number_or_tag(BlockNumberOrTag::Latest)
@@ -1165,24 +2779,77 @@ pub async fn get_sequencer_commitments_and_blocks(
             .inner()
             .inner()
             .number;
-        (Some(block), None, None, None)
+        Ok((Some(block), None, None, None))
     } else {
         panic!("Invalid chain ID");
     }
 }
-/// Fetches the current sequencer commitment for L2 chains.
+
+/// Verifies a caller-supplied sequencer commitment, or fetches the current one if none is given.
 ///
 /// # Arguments
 /// * `chain_id` - Chain ID (Optimism, Base, or their Sepolia variants).
+/// * `sequencer_commitment` - Commitment to use instead of fetching one.
 ///
 /// # Returns
-/// * `(SequencerCommitment, u64)` - Tuple of sequencer commitment and block number.
+/// * `Result<(SequencerCommitment, u64), Error>` - Tuple of sequencer commitment and block number.
+///
+/// # Errors
+/// Returns `MaldaError::InvalidSequencerCommitment` if the supplied commitment is not signed by
+/// the chain's sequencer or doesn't decode to an execution payload.
 ///
 /// # Panics
 /// Panics if:
 /// - Invalid chain ID is provided.
 /// - Sequencer API request fails.
-pub async fn get_current_sequencer_commitment(chain_id: u64) -> (SequencerCommitment, u64) {
+pub async fn get_or_verify_sequencer_commitment(
+    chain_id: u64,
+    sequencer_commitment: Option<SequencerCommitment>,
+) -> Result<(SequencerCommitment, u64), Error> {
+    let Some(commitment) = sequencer_commitment else {
+        return get_current_sequencer_commitment(chain_id).await;
+    };
+
+    commitment
+        .verify_any(opstack_sequencers(chain_id), chain_id)
+        .map_err(|err| anyhow::anyhow!("{:#}", err))
+        .context(MaldaError::InvalidSequencerCommitment { chain_id })?;
+    let block = ExecutionPayload::try_from(&commitment)
+        .map_err(|err| anyhow::anyhow!("{:#}", err))
+        .context(MaldaError::InvalidSequencerCommitment { chain_id })?
+        .block_number;
+
+    Ok((commitment, block))
+}
+
+/// Returns the HTTP client shared by all sequencer commitment requests.
+///
+/// Reusing one client keeps connections to the sequencer endpoints pooled, so a batch over
+/// several chains doesn't repeat the TLS handshake per request.
+fn http_client() -> &'static reqwest::Client {
+    static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Fetches the current sequencer commitment for L2 chains.
+///
+/// # Arguments
+/// * `chain_id` - Chain ID (Optimism, Base, or their Sepolia variants).
+///
+/// # Returns
+/// * `Result<(SequencerCommitment, u64), Error>` - Tuple of sequencer commitment and block number.
+///
+/// # Errors
+/// Returns an error if:
+/// - The request to the sequencer endpoint fails.
+/// - The endpoint answers with a non-success status.
+/// - The response can't be parsed, see `parse_sequencer_commitment`.
+///
+/// # Panics
+/// Panics if an invalid chain ID is provided.
+pub async fn get_current_sequencer_commitment(
+    chain_id: u64,
+) -> Result<(SequencerCommitment, u64), Error> {
     let req = match chain_id {
         BASE_CHAIN_ID => sequencer_request_base(),
         OPTIMISM_CHAIN_ID => sequencer_request_optimism(),
@@ -1191,18 +2858,85 @@ pub async fn get_current_sequencer_commitment(chain_id: u64) -> (SequencerCommit
         _ => panic!("Invalid chain ID: {}", chain_id),
     };
 
-    let commitment = reqwest::get(req)
-        .await
-        .expect("Failed to fetch sequencer commitment")
-        .json::<SequencerCommitment>()
-        .await
-        .expect("Failed to parse sequencer commitment JSON");
+    let response = http_client().get(req).send().await.with_context(|| {
+        format!(
+            "Failed to fetch sequencer commitment for chain {}",
+            chain_id
+        )
+    })?;
+    let status = response.status();
+    let body = response.text().await.with_context(|| {
+        format!(
+            "Failed to read sequencer commitment response for chain {}",
+            chain_id
+        )
+    })?;
+    if !status.is_success() {
+        anyhow::bail!(
+            "Sequencer endpoint for chain {} returned {}: {}",
+            chain_id,
+            status,
+            body.trim()
+        );
+    }
 
+    let commitment = parse_sequencer_commitment(&body).with_context(|| {
+        format!(
+            "Failed to parse sequencer commitment for chain {}",
+            chain_id
+        )
+    })?;
     let block = ExecutionPayload::try_from(&commitment)
-        .expect("Failed to convert commitment to execution payload")
+        .map_err(|err| anyhow::anyhow!("{:#}", err))
+        .with_context(|| {
+            format!(
+                "Sequencer commitment for chain {} is not an execution payload",
+                chain_id
+            )
+        })?
         .block_number;
 
-    (commitment, block)
+    Ok((commitment, block))
+}
+
+/// Parses a sequencer endpoint response into a commitment.
+///
+/// Accepts the commitment as a JSON object with `data` and `signature`, or as the compressed
+/// commitment encoded in hex (with or without `0x`) or base64, either as plain text or as a
+/// JSON string. JSON-RPC style `{"result": ...}` wrappers are unwrapped first.
+///
+/// # Arguments
+/// * `body` - Response body of the sequencer endpoint.
+///
+/// # Returns
+/// * `Result<SequencerCommitment, Error>` - The parsed commitment.
+///
+/// # Errors
+/// Returns an error if the body matches none of the accepted formats or the compressed
+/// commitment can't be decoded.
+pub fn parse_sequencer_commitment(body: &str) -> Result<SequencerCommitment, Error> {
+    use base64::Engine;
+
+    let body = body.trim();
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            if let Some(result) = object.remove("result") {
+                return parse_sequencer_commitment(&result.to_string());
+            }
+            return serde_json::from_value(serde_json::Value::Object(object))
+                .context("JSON response is not a sequencer commitment");
+        }
+        Ok(serde_json::Value::String(encoded)) => return parse_sequencer_commitment(&encoded),
+        _ => {}
+    }
+
+    let compressed = match hex::decode(body.strip_prefix("0x").unwrap_or(body)) {
+        Ok(compressed) => compressed,
+        Err(_) => base64::engine::general_purpose::STANDARD
+            .decode(body)
+            .context("response is neither JSON, hex nor base64")?,
+    };
+    SequencerCommitment::new(&compressed).map_err(|err| anyhow::anyhow!("{:#}", err))
 }
 
 /// Retrieves L1 block information for L2 chains.
@@ -1229,43 +2963,82 @@ pub async fn get_l1block_call_input(
         OPTIMISM_SEPOLIA_CHAIN_ID => rpc_url_optimism_sepolia(),
         _ => panic!("Invalid chain ID for L1 block call: {}", chain_id),
     };
-    let mut env = EthEvmEnv::builder()
-        .rpc(Url::parse(rpc_url).expect("Failed to parse RPC URL"))
-        .block_number_or_tag(block)
-        .build()
-        .await
-        .expect("Failed to build EVM environment");
+    // The hash input and the number lookup use separate envs, so they are fetched concurrently.
+    let hash_input = async {
+        let mut env = EthEvmEnv::builder()
+            .rpc(Url::parse(rpc_url).expect("Failed to parse RPC URL"))
+            .block_number_or_tag(block)
+            .build()
+            .await
+            .expect("Failed to build EVM environment");
 
-    let call = IL1Block::hashCall {};
-    let mut contract = Contract::preflight(L1_BLOCK_ADDRESS_OPSTACK, &mut env);
-    contract
-        .call_builder(&call)
-        .call()
-        .await
-        .expect("Failed to call L1Block hash");
+        let call = IL1Block::hashCall {};
+        let mut contract = Contract::preflight(l1_block_address(chain_id), &mut env);
+        contract
+            .call_builder(&call)
+            .call()
+            .await
+            .expect("Failed to call L1Block hash");
 
-    let view_call_input_l1_block = env
-        .into_input()
-        .await
-        .expect("Failed to convert environment to input");
+        env.into_input()
+            .await
+            .expect("Failed to convert environment to input")
+    };
 
-    let mut env = EthEvmEnv::builder()
+    let l1_block_number = async {
+        let mut env = EthEvmEnv::builder()
+            .rpc(Url::parse(rpc_url).expect("Failed to parse RPC URL"))
+            .block_number_or_tag(block)
+            .build()
+            .await
+            .expect("Failed to build EVM environment");
+
+        let call = IL1Block::numberCall {};
+        let mut contract = Contract::preflight(l1_block_address(chain_id), &mut env);
+        contract
+            .call_builder(&call)
+            .call()
+            .await
+            .expect("Failed to call L1Block number")
+            ._0
+    };
+
+    let (view_call_input_l1_block, l1_block) = tokio::join!(hash_input, l1_block_number);
+
+    (view_call_input_l1_block, l1_block)
+}
+
+/// Returns the number of a chain's safe block, at most the reorg protection depth behind `block`.
+///
+/// # Arguments
+/// * `chain_id` - Chain ID of the chain.
+/// * `rpc_url` - RPC endpoint URL of the chain.
+/// * `block` - Anchored head the safe block is linked to.
+///
+/// # Returns
+/// * `Result<u64, Error>` - The safe block number.
+///
+/// # Errors
+/// Returns an error if the chain is not supported or the safe block cannot be fetched.
+///
+/// # Panics
+/// Panics if the RPC URL cannot be parsed.
+async fn get_safe_block_number(chain_id: u64, rpc_url: &str, block: u64) -> Result<u64, Error> {
+    let reorg_protection_depth =
+        reorg_protection_depth(chain_id).map_err(|err| anyhow::anyhow!("{:#}", err))?;
+
+    let safe_block = EthEvmEnv::builder()
         .rpc(Url::parse(rpc_url).expect("Failed to parse RPC URL"))
-        .block_number_or_tag(block)
+        .block_number_or_tag(BlockNumberOrTag::Safe)
         .build()
         .await
-        .expect("Failed to build EVM environment");
+        .context("Failed to fetch safe block")?
+        .header()
+        .inner()
+        .inner()
+        .number;
 
-    let call = IL1Block::numberCall {};
-    let mut contract = Contract::preflight(L1_BLOCK_ADDRESS_OPSTACK, &mut env);
-    let l1_block = contract
-        .call_builder(&call)
-        .call()
-        .await
-        .expect("Failed to call L1Block number")
-        ._0;
-
-    (view_call_input_l1_block, l1_block)
+    Ok(safe_block.min(block - reorg_protection_depth))
 }
 
 /// Fetches a sequence of blocks for reorg protection.
@@ -1287,20 +3060,33 @@ pub async fn get_linking_blocks(
     rpc_url: &str,
     current_block: u64,
 ) -> Vec<RlpHeader<Header>> {
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        _ => panic!("Invalid chain ID: {}", chain_id),
-    };
+    let reorg_protection_depth = reorg_protection_depth(chain_id).expect("invalid chain id");
 
-    let start_block = current_block - reorg_protection_depth + 1;
+    get_linking_blocks_from(
+        rpc_url,
+        current_block - reorg_protection_depth + 1,
+        current_block,
+    )
+    .await
+}
 
+/// Fetches the blocks from `start_block` up to and including `current_block`.
+///
+/// # Arguments
+/// * `rpc_url` - RPC endpoint URL.
+/// * `start_block` - First block to fetch.
+/// * `current_block` - Last block to fetch.
+///
+/// # Returns
+/// * `Vec<RlpHeader<Header>>` - Vector of block headers.
+///
+/// # Panics
+/// Panics if RPC calls fail.
+pub async fn get_linking_blocks_from(
+    rpc_url: &str,
+    start_block: u64,
+    current_block: u64,
+) -> Vec<RlpHeader<Header>> {
     // Create futures for parallel block fetching
     let futures: Vec<_> = (start_block..=current_block)
         .map(|block_nr| {
@@ -1324,3 +3110,97 @@ pub async fn get_linking_blocks(
         .map(|r| r.expect("Failed to join block fetch task"))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::HeaderBuilder;
+    use crate::validators::{validate_chain_length, validate_chain_numbers, ChainHeader};
+
+    #[test]
+    fn test_buffered_query_block_links_to_anchor() {
+        let block = 1_000;
+        let buffer = 5;
+        let reorg_depth = Chain::Ethereum.reorg_depth();
+
+        let query_block = buffered_query_block(block, reorg_depth, None, buffer).unwrap();
+        assert_eq!(query_block, block - reorg_depth - buffer);
+
+        // The host fetches the linking blocks from `query_block + 1` up to `block`
+        let query_header = HeaderBuilder::new().number(query_block).build();
+        let linking_blocks = HeaderBuilder::new()
+            .parent_hash(query_header.hash_slow())
+            .number(query_block + 1)
+            .linked_chain(block - query_block);
+        assert_eq!(linking_blocks.len() as u64, reorg_depth + buffer);
+
+        validate_chain_numbers(query_block, &linking_blocks);
+        validate_chain_length(
+            ETHEREUM_CHAIN_ID,
+            query_header.hash_slow(),
+            &linking_blocks,
+            linking_blocks.last().unwrap().hash_slow(),
+        );
+    }
+
+    #[test]
+    fn test_buffered_query_block_below_safe_block() {
+        assert_eq!(buffered_query_block(1_000, 0, Some(990), 5).unwrap(), 985);
+    }
+
+    #[test]
+    fn test_buffered_query_block_below_genesis_fails() {
+        assert!(buffered_query_block(3, 0, None, 5).is_err());
+        assert!(buffered_query_block(3, 4, None, 0).is_err());
+    }
+
+    #[test]
+    fn test_classify_bonsai_error() {
+        use bonsai_sdk::SdkErr;
+
+        assert_eq!(
+            classify_bonsai_error(&SdkErr::MissingApiKey),
+            BonsaiErrorKind::Permanent
+        );
+        assert_eq!(
+            classify_bonsai_error(&SdkErr::InternalServerErr("Quota exceeded".into())),
+            BonsaiErrorKind::Permanent
+        );
+        assert_eq!(
+            classify_bonsai_error(&SdkErr::InternalServerErr("502 Bad Gateway".into())),
+            BonsaiErrorKind::Transient
+        );
+
+        let permanent = MaldaError::Bonsai {
+            kind: BonsaiErrorKind::Permanent,
+            message: "Quota exceeded".into(),
+        };
+        assert!(permanent.is_permanent());
+        assert!(!MaldaError::PreflightFailed {
+            chain_id: BASE_CHAIN_ID,
+            attempts: 3
+        }
+        .is_permanent());
+    }
+
+    #[test]
+    fn test_reject_empty_and_unsupported_batches() {
+        let err = ensure_single_network(&[]).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::EmptyBatch)
+        );
+
+        let err = ensure_single_network(&[LINEA_CHAIN_ID, 1234]).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::UnsupportedChain { chain_id: 1234 })
+        );
+
+        let err = ensure_targets_on_network(Network::Mainnet, &[vec![1234]]).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::UnsupportedChain { chain_id: 1234 })
+        );
+    }
+}
@@ -35,15 +35,19 @@ use risc0_steel::{
     serde::RlpHeader,
     Contract, EvmInput,
 };
-use risc0_zkvm::{default_executor, default_prover, ExecutorEnv, ProveInfo, SessionInfo};
+use risc0_zkvm::{
+    default_executor, default_prover, ExecutorEnv, ExecutorEnvBuilder, ProveInfo, SessionInfo,
+};
 
 use anyhow::Error;
 use tokio;
+use tracing::info;
 use url::Url;
 
 use crate::constants::*;
 use crate::elfs_ids::GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ELF;
-use crate::types::{IMaldaMarket, SequencerCommitment};
+use crate::types::{BlockSafety, IMaldaMarket, SequencerCommitment};
+use crate::viewcalls::get_proof_data_input;
 
 /// Generates a zero-knowledge proof for a user's proof data query.
 ///
@@ -136,38 +140,153 @@ pub async fn get_proof_data_zkvm_env(
     chain_id: u64,
     trusted_hash: B256,
 ) -> ExecutorEnv<'static> {
-    let (rpc_url, rpc_url_beacon) = match chain_id {
-        ETHEREUM_CHAIN_ID => (rpc_url_ethereum(), rpc_url_beacon()),
+    get_proof_data_zkvm_env_after(&[], user, market, chain_id, trusted_hash).await
+}
+
+/// Builds the combined guest environment: the sequencer path input followed by the light
+/// client input.
+///
+/// The first part is laid out exactly as for `GET_PROOF_DATA_ELF` and the second exactly as
+/// for `GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ELF`, so one proof of the combined guest carries
+/// both anchors.
+///
+/// # Arguments
+///
+/// * `users` - User address vectors for the sequencer path, one per chain
+/// * `markets` - Market address vectors for the sequencer path, one per chain
+/// * `target_chain_ids` - Target chain ID vectors for the sequencer path, one per chain
+/// * `chain_ids` - Chain IDs for the sequencer path
+/// * `safety` - Block the sequencer path anchors to, see `BlockSafety`
+/// * `user` - The user's Ethereum address for the light client path
+/// * `market` - The market contract address for the light client path
+/// * `trusted_hash` - The trusted beacon chain block hash to anchor verification from
+///
+/// # Returns
+///
+/// Returns an `ExecutorEnv` for the combined guest
+///
+/// # Errors
+///
+/// Returns an error if building the sequencer path input fails
+///
+/// # Panics
+///
+/// Panics if fetching the light client input fails
+pub async fn get_proof_data_combined_zkvm_env(
+    users: Vec<Vec<Address>>,
+    markets: Vec<Vec<Address>>,
+    target_chain_ids: Vec<Vec<u64>>,
+    chain_ids: Vec<u64>,
+    safety: impl Into<BlockSafety>,
+    user: Address,
+    market: Address,
+    trusted_hash: B256,
+) -> Result<ExecutorEnv<'static>, Error> {
+    let sequencer_input =
+        get_proof_data_input(users, markets, target_chain_ids, chain_ids, safety.into()).await?;
+
+    Ok(get_proof_data_zkvm_env_after(
+        &sequencer_input,
+        user,
+        market,
+        ETHEREUM_CHAIN_ID,
+        trusted_hash,
+    )
+    .await)
+}
+
+/// Builds a zkVM environment for a proof data query using the given beacon RPC clients.
+///
+/// Like `get_proof_data_zkvm_env`, but with caller-provided clients of any `ConsensusRpc`
+/// implementation in place of the `NimbusRpc` clients built from the environment. The light
+/// client data is fetched from the first client that serves it completely.
+///
+/// # Arguments
+///
+/// * `beacon_rpcs` - Beacon RPC clients, in order of preference
+/// * `user` - The user's Ethereum address
+/// * `market` - The market contract address to query
+/// * `chain_id` - The target chain identifier
+/// * `trusted_hash` - The trusted beacon chain block hash to anchor verification from
+///
+/// # Returns
+///
+/// Returns an `ExecutorEnv` configured for generating proof data query proofs
+///
+/// # Panics
+///
+/// Panics if an unsupported chain ID is provided or no client serves the light client data
+pub async fn get_proof_data_zkvm_env_with_rpc<R: ConsensusRpc>(
+    beacon_rpcs: &[R],
+    user: Address,
+    market: Address,
+    chain_id: u64,
+    trusted_hash: B256,
+) -> ExecutorEnv<'static> {
+    get_proof_data_zkvm_env_after_with_rpc(&[], beacon_rpcs, user, market, chain_id, trusted_hash)
+        .await
+}
+
+/// Fetches the light client input and builds the environment with `prefix` written first.
+async fn get_proof_data_zkvm_env_after(
+    prefix: &[u8],
+    user: Address,
+    market: Address,
+    chain_id: u64,
+    trusted_hash: B256,
+) -> ExecutorEnv<'static> {
+    let beacon_rpcs: Vec<NimbusRpc> = match chain_id {
+        ETHEREUM_CHAIN_ID => rpc_urls_beacon().into_iter().map(NimbusRpc::new).collect(),
         _ => panic!("Invalid chain ID"),
     };
 
-    let beacon_rpc = NimbusRpc::new(rpc_url_beacon);
-    let beacon_root = OldB256::from(trusted_hash.0);
-    let bootstrap: Bootstrap = beacon_rpc.get_bootstrap(beacon_root).await.unwrap();
-    let current_period = calc_sync_period(bootstrap.header.beacon.slot);
+    get_proof_data_zkvm_env_after_with_rpc(
+        prefix,
+        &beacon_rpcs,
+        user,
+        market,
+        chain_id,
+        trusted_hash,
+    )
+    .await
+}
 
-    let updates: Vec<Update> = beacon_rpc.get_updates(current_period, 10).await.unwrap();
-    let finality_update = beacon_rpc.get_optimistic_update().await.unwrap();
+/// Fetches the light client input from `beacon_rpcs` and builds the environment with
+/// `prefix` written first.
+async fn get_proof_data_zkvm_env_after_with_rpc<R: ConsensusRpc>(
+    prefix: &[u8],
+    beacon_rpcs: &[R],
+    user: Address,
+    market: Address,
+    chain_id: u64,
+    trusted_hash: B256,
+) -> ExecutorEnv<'static> {
+    let rpc_url = match chain_id {
+        ETHEREUM_CHAIN_ID => rpc_url_ethereum(),
+        _ => panic!("Invalid chain ID"),
+    };
 
-    // let current_beacon_root = finality_update.attested_header.tree_root_hash();
-    let beacon_block_slot = finality_update.attested_header.beacon.slot;
-    let beacon_block = beacon_rpc.get_block(beacon_block_slot).await.unwrap();
-    let block = beacon_block.body.execution_payload().block_number().clone();
+    let beacon_root = OldB256::from(trusted_hash.0);
+    let LightClientInput {
+        bootstrap,
+        updates,
+        finality_update,
+        block,
+    } = get_light_client_input(beacon_rpcs, beacon_root)
+        .await
+        .unwrap_or_else(|err| panic!("{:#}", err));
 
     let linking_blocks = get_linking_blocks(chain_id, rpc_url, block).await;
     let proof_data_call_input =
         get_proof_data_call_input(chain_id, rpc_url, block, user, market).await;
 
-    let beacon_proof_data_input = get_proof_data_call_input(
-        chain_id,
-        rpc_url,
-        block + REORG_PROTECTION_DEPTH_ETHEREUM,
-        user,
-        market,
-    )
-    .await;
+    let beacon_proof_data_input =
+        get_beacon_call_input_at_head(chain_id, rpc_url, block, user, market).await;
 
-    build_l1_chain_builder_environment(
+    let mut env = ExecutorEnv::builder();
+    env.write_slice(prefix);
+    write_l1_chain_builder_input(
+        &mut env,
         proof_data_call_input,
         chain_id,
         user,
@@ -180,7 +299,145 @@ pub async fn get_proof_data_zkvm_env(
         updates,
         finality_update,
         beacon_proof_data_input,
+    );
+    env.build().unwrap()
+}
+
+/// Light client data fetched from a beacon node for one proof.
+struct LightClientInput {
+    bootstrap: Bootstrap,
+    updates: Vec<Update>,
+    finality_update: OptimisticUpdate,
+    /// Execution block number of the optimistic head
+    block: u64,
+}
+
+/// Fetches the light client data from the first beacon RPC client that serves all of it.
+///
+/// # Arguments
+///
+/// * `beacon_rpcs` - Beacon RPC clients, in order of preference
+/// * `beacon_root` - Trusted beacon block root to bootstrap from
+///
+/// # Errors
+///
+/// Returns the last client's error if no client serves the data, or an error if
+/// `beacon_rpcs` is empty
+async fn get_light_client_input<R: ConsensusRpc>(
+    beacon_rpcs: &[R],
+    beacon_root: OldB256,
+) -> Result<LightClientInput, Error> {
+    let mut last_err = anyhow::anyhow!("no beacon RPC configured");
+    for (index, beacon_rpc) in beacon_rpcs.iter().enumerate() {
+        match get_light_client_input_from(beacon_rpc, beacon_root).await {
+            Ok(input) => return Ok(input),
+            Err(err) => {
+                info!("Beacon RPC {} failed: {:#}", index, err);
+                last_err = err;
+            }
+        }
+    }
+    Err(last_err)
+}
+
+/// Fetches the light client data from a single beacon RPC client.
+async fn get_light_client_input_from<R: ConsensusRpc>(
+    beacon_rpc: &R,
+    beacon_root: OldB256,
+) -> Result<LightClientInput, Error> {
+    let bootstrap: Bootstrap = beacon_rpc
+        .get_bootstrap(beacon_root)
+        .await
+        .map_err(|err| anyhow::anyhow!("failed to fetch bootstrap: {:#}", err))?;
+    let current_period = calc_sync_period(bootstrap.header.beacon.slot);
+
+    let updates: Vec<Update> = beacon_rpc
+        .get_updates(current_period, 10)
+        .await
+        .map_err(|err| anyhow::anyhow!("failed to fetch updates: {:#}", err))?;
+    let finality_update = beacon_rpc
+        .get_optimistic_update()
+        .await
+        .map_err(|err| anyhow::anyhow!("failed to fetch optimistic update: {:#}", err))?;
+
+    let beacon_block_slot = finality_update.attested_header.beacon.slot;
+    let block = get_execution_block_number(beacon_rpc, beacon_block_slot).await?;
+
+    Ok(LightClientInput {
+        bootstrap,
+        updates,
+        finality_update,
+        block,
+    })
+}
+
+/// Constructs the beacon committed EVM input at the anchored head block.
+///
+/// The queried state sits `reorg_protection_depth` blocks below the head, but only the head
+/// is tied to the light client: the guest checks that this input's beacon commitment equals
+/// the verified beacon root and that its header is the last linking block, which in turn
+/// links back to the queried state. `get_proof_data_call_input` always queries the depth
+/// below the block it is given, so the head is passed shifted up by that same depth.
+///
+/// # Arguments
+///
+/// * `chain_id` - The target chain identifier
+/// * `chain_url` - RPC endpoint URL for the target chain
+/// * `head` - The anchored head block number
+/// * `user` - Address of the user to query
+/// * `market` - Token contract address to query
+///
+/// # Returns
+///
+/// Returns an `EvmInput` at `head` committed to its beacon block root
+///
+/// # Panics
+///
+/// Panics if an unsupported chain ID is provided
+async fn get_beacon_call_input_at_head(
+    chain_id: u64,
+    chain_url: &str,
+    head: u64,
+    user: Address,
+    market: Address,
+) -> EvmInput<RlpHeader<Header>> {
+    let reorg_protection_depth = reorg_protection_depth(chain_id).expect("invalid chain id");
+    get_proof_data_call_input(
+        chain_id,
+        chain_url,
+        head + reorg_protection_depth,
+        user,
+        market,
     )
+    .await
+}
+
+/// Returns the execution block number of the beacon block at a slot.
+///
+/// The slot is the attested header's, which is a real beacon block, so no earlier slot is
+/// ever a valid substitute: its execution block would not be the one the verified beacon
+/// root commits to.
+///
+/// # Arguments
+///
+/// * `beacon_rpc` - Beacon node RPC client
+/// * `slot` - Slot of the attested beacon block
+///
+/// # Returns
+///
+/// Returns the block number of the beacon block's execution payload
+///
+/// # Errors
+///
+/// Returns an error if the beacon block can't be fetched
+pub async fn get_execution_block_number<R: ConsensusRpc>(
+    beacon_rpc: &R,
+    slot: u64,
+) -> Result<u64, Error> {
+    let beacon_block = beacon_rpc.get_block(slot).await.map_err(|err| {
+        anyhow::anyhow!("failed to fetch beacon block at slot {}: {:#}", slot, err)
+    })?;
+    Ok(*beacon_block.body.execution_payload().block_number())
 }
 
 /// Constructs an EVM input for a proof data query.
@@ -206,19 +463,7 @@ pub async fn get_proof_data_call_input(
     user: Address,
     market: Address,
 ) -> EvmInput<RlpHeader<Header>> {
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        SCROLL_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        SCROLL_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
+    let reorg_protection_depth = reorg_protection_depth(chain_id).expect("invalid chain id");
 
     let block_reorg_protected = block - reorg_protection_depth;
 
@@ -265,19 +510,7 @@ pub async fn get_linking_blocks(
     rpc_url: &str,
     current_block: u64,
 ) -> Vec<RlpHeader<Header>> {
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        SCROLL_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        SCROLL_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
+    let reorg_protection_depth = reorg_protection_depth(chain_id).expect("invalid chain id");
 
     let mut linking_blocks = vec![];
 
@@ -323,6 +556,43 @@ pub fn build_l1_chain_builder_environment(
     beacon_input: EvmInput<RlpHeader<Header>>,
 ) -> risc0_zkvm::ExecutorEnv<'static> {
     let mut env = risc0_zkvm::ExecutorEnv::builder();
+    write_l1_chain_builder_input(
+        &mut env,
+        view_call_input,
+        chain_id,
+        user,
+        market,
+        sequencer_commitment,
+        env_op_input,
+        linking_blocks,
+        bootstrap,
+        checkpoint,
+        updates,
+        finality_update,
+        beacon_input,
+    );
+    env.build().unwrap()
+}
+
+/// Writes the L1 chain verification input to an existing environment builder.
+///
+/// Same layout as `build_l1_chain_builder_environment`, for guests that read other input
+/// before the light client input.
+pub fn write_l1_chain_builder_input(
+    env: &mut ExecutorEnvBuilder<'static>,
+    view_call_input: EvmInput<RlpHeader<Header>>,
+    chain_id: u64,
+    user: Address,
+    market: Address,
+    sequencer_commitment: Option<SequencerCommitment>,
+    env_op_input: Option<EthEvmInput>,
+    linking_blocks: Vec<RlpHeader<Header>>,
+    bootstrap: Bootstrap,
+    checkpoint: OldB256,
+    updates: Vec<Update>,
+    finality_update: OptimisticUpdate,
+    beacon_input: EvmInput<RlpHeader<Header>>,
+) {
     env.write(&view_call_input)
         .unwrap()
         .write(&chain_id)
@@ -365,6 +635,4 @@ pub fn build_l1_chain_builder_environment(
     }
 
     env.write(&beacon_input).unwrap();
-
-    env.build().unwrap()
 }
@@ -14,8 +14,14 @@ mod tests {
         providers::{Provider, ProviderBuilder},
         transports::http::reqwest::Url,
     };
-    use alloy_primitives::{address, Address};
-    use malda_rs::{constants::*, validators::*, viewcalls::*};
+    use alloy_primitives::{address, Address, Bytes, PrimitiveSignature, U256};
+    use malda_rs::{
+        constants::*,
+        cryptography::{recover_signer, signature_msg},
+        types::SequencerCommitment,
+        validators::*,
+        viewcalls::*,
+    };
     use risc0_steel::{
         ethereum::EthEvmEnv, host::BlockNumberOrTag as BlockRisc0, serde::RlpHeader,
     };
@@ -53,10 +59,11 @@ mod tests {
             latest_block,
             vec![USER],
             vec![WETH_MARKET_SEPOLIA],
-            vec![OPTIMISM_CHAIN_ID],
+            vec![vec![OPTIMISM_CHAIN_ID]],
             false,
         )
-        .await;
+        .await
+        .unwrap();
 
         let env = proof_data_call_input.0.as_ref().unwrap().clone().into_env();
         validate_linea_env(LINEA_CHAIN_ID, &env.header().inner().clone());
@@ -90,10 +97,11 @@ mod tests {
             latest_block,
             vec![USER],
             vec![WETH_MARKET_SEPOLIA],
-            vec![LINEA_CHAIN_ID],
+            vec![vec![LINEA_CHAIN_ID]],
             false,
         )
-        .await;
+        .await
+        .unwrap();
 
         let env = proof_data_call_input.0.as_ref().unwrap().clone().into_env();
         assert!(std::panic::catch_unwind(|| {
@@ -130,10 +138,11 @@ mod tests {
             latest_block,
             vec![USER],
             vec![WETH_MARKET_SEPOLIA],
-            vec![OPTIMISM_CHAIN_ID],
+            vec![vec![OPTIMISM_CHAIN_ID]],
             false,
         )
-        .await;
+        .await
+        .unwrap();
 
         let env = proof_data_call_input.0.as_ref().unwrap().clone().into_env();
         let mut header = env.header().inner().inner().clone();
@@ -155,8 +164,9 @@ mod tests {
     /// - No panic occurs with valid input
     #[tokio::test]
     async fn test_validate_optimism_env_correct_input() {
-        let (sequencer_commitment, block) =
-            get_current_sequencer_commitment(OPTIMISM_CHAIN_ID).await;
+        let (sequencer_commitment, block) = get_current_sequencer_commitment(OPTIMISM_CHAIN_ID)
+            .await
+            .unwrap();
 
         let http_url: Url = rpc_url_optimism().parse().unwrap();
 
@@ -183,8 +193,9 @@ mod tests {
     /// - Panics due to hash mismatch
     #[tokio::test]
     async fn test_validate_optimism_env_wrong_hash_panics() {
-        let (sequencer_commitment, block) =
-            get_current_sequencer_commitment(OPTIMISM_CHAIN_ID).await;
+        let (sequencer_commitment, block) = get_current_sequencer_commitment(OPTIMISM_CHAIN_ID)
+            .await
+            .unwrap();
 
         let http_url: Url = rpc_url_optimism().parse().unwrap();
 
@@ -216,8 +227,9 @@ mod tests {
     /// - Panics due to chain ID mismatch
     #[tokio::test]
     async fn test_validate_optimism_env_wrong_chain_id_panics() {
-        let (sequencer_commitment, block) =
-            get_current_sequencer_commitment(OPTIMISM_CHAIN_ID).await;
+        let (sequencer_commitment, block) = get_current_sequencer_commitment(OPTIMISM_CHAIN_ID)
+            .await
+            .unwrap();
 
         let http_url: Url = rpc_url_optimism().parse().unwrap();
 
@@ -250,7 +262,9 @@ mod tests {
     #[tokio::test]
     async fn test_validate_optimism_env_wrong_commitment_panics() {
         // get commitment from base chain here
-        let (sequencer_commitment, block) = get_current_sequencer_commitment(BASE_CHAIN_ID).await;
+        let (sequencer_commitment, block) = get_current_sequencer_commitment(BASE_CHAIN_ID)
+            .await
+            .unwrap();
 
         let http_url: Url = rpc_url_optimism().parse().unwrap();
 
@@ -282,11 +296,13 @@ mod tests {
     /// - Panics for both signature and data manipulation
     #[tokio::test]
     async fn test_validate_optimism_env_manipulated_commitment_panics() {
-        let (sequencer_commitment, _block) =
-            get_current_sequencer_commitment(OPTIMISM_CHAIN_ID).await;
+        let (sequencer_commitment, _block) = get_current_sequencer_commitment(OPTIMISM_CHAIN_ID)
+            .await
+            .unwrap();
 
-        let (wrong_sequencer_commitment, block) =
-            get_current_sequencer_commitment(BASE_CHAIN_ID).await;
+        let (wrong_sequencer_commitment, block) = get_current_sequencer_commitment(BASE_CHAIN_ID)
+            .await
+            .unwrap();
 
         let mut manipulated_commitment_signature = sequencer_commitment.clone();
         manipulated_commitment_signature.signature = wrong_sequencer_commitment.signature;
@@ -416,4 +432,122 @@ mod tests {
         })
         .is_err());
     }
+
+    /// Tests that a caller-supplied sequencer commitment is verified before use
+    ///
+    /// # Test Steps
+    /// 1. Builds a commitment that is not signed by the Optimism Sepolia sequencer
+    /// 2. Passes it as a pre-fetched commitment
+    ///
+    /// # Expected Outcome
+    /// - Returns `MaldaError::InvalidSequencerCommitment` without fetching from the sequencer
+    #[tokio::test]
+    async fn test_supplied_sequencer_commitment_with_wrong_signer_is_rejected() {
+        let commitment = SequencerCommitment {
+            data: Bytes::from(vec![0u8; 32]),
+            signature: PrimitiveSignature::new(U256::from(1), U256::from(1), false),
+        };
+
+        let err = get_or_verify_sequencer_commitment(OPTIMISM_SEPOLIA_CHAIN_ID, Some(commitment))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::InvalidSequencerCommitment {
+                chain_id: OPTIMISM_SEPOLIA_CHAIN_ID
+            })
+        );
+    }
+
+    /// Tests that live OpStack mainnet commitments are signed by the known sequencers
+    ///
+    /// # Test Steps
+    /// 1. Fetches the current Optimism and Base sequencer commitments
+    /// 2. Recovers the signer from `signature_msg` over the payload
+    ///
+    /// # Expected Outcome
+    /// - The signers are `OPTIMISM_SEQUENCER` and `BASE_SEQUENCER`
+    /// - Verifying against the other chain ID fails
+    #[tokio::test]
+    async fn test_mainnet_sequencer_commitments_recover_known_sequencers() {
+        for (chain_id, sequencer, other_chain_id) in [
+            (OPTIMISM_CHAIN_ID, OPTIMISM_SEQUENCER, BASE_CHAIN_ID),
+            (BASE_CHAIN_ID, BASE_SEQUENCER, OPTIMISM_CHAIN_ID),
+        ] {
+            let (commitment, _) = get_current_sequencer_commitment(chain_id).await.unwrap();
+
+            let sighash = signature_msg(&commitment.data, chain_id);
+            assert_eq!(
+                recover_signer(commitment.signature, sighash),
+                Some(sequencer)
+            );
+            commitment.verify(sequencer, chain_id).unwrap();
+            assert!(commitment.verify(sequencer, other_chain_id).is_err());
+        }
+    }
+
+    /// Tests that sequencer endpoint responses parse in every accepted format
+    ///
+    /// # Expected Outcome
+    /// - A JSON commitment, hex with and without `0x`, a JSON string, a JSON-RPC result and
+    ///   base64 all yield the same commitment
+    /// - Non-commitment bodies and commitments shorter than a signature are rejected
+    #[test]
+    fn test_parse_sequencer_commitment_formats() {
+        use base64::Engine;
+
+        let mut raw = vec![1u8; 64];
+        raw.push(0);
+        raw.extend_from_slice(b"payload");
+        let compressed = snap::raw::Encoder::new().compress_vec(&raw).unwrap();
+        let expected = SequencerCommitment::new(&compressed).unwrap();
+
+        let hex_body = hex::encode(&compressed);
+        for body in [
+            serde_json::to_string(&expected).unwrap(),
+            hex_body.clone(),
+            format!("0x{}", hex_body),
+            format!("\"0x{}\"\n", hex_body),
+            format!("{{\"result\":\"0x{}\"}}", hex_body),
+            base64::engine::general_purpose::STANDARD.encode(&compressed),
+        ] {
+            let parsed = parse_sequencer_commitment(&body).unwrap();
+            assert_eq!(parsed.data, expected.data);
+            assert_eq!(parsed.signature, expected.signature);
+        }
+
+        assert!(parse_sequencer_commitment("<html>bad gateway</html>").is_err());
+        let short = snap::raw::Encoder::new().compress_vec(&[0u8; 10]).unwrap();
+        assert!(parse_sequencer_commitment(&hex::encode(short)).is_err());
+    }
+
+    /// Tests that the standalone and the env input helpers read the same posted Linea block
+    ///
+    /// # Test Steps
+    /// 1. Reads the last Linea block posted to L1 at the current L1 block via the env input
+    /// 2. Reads it again at the latest L1 block via `get_linea_l1_posted_block`
+    ///
+    /// # Expected Outcome
+    /// - The env input is built and the posted block never goes backwards
+    #[tokio::test]
+    async fn test_linea_l1_posted_block_matches_env_input() {
+        let http_url: Url = rpc_url_ethereum().parse().unwrap();
+        let l1_block = ProviderBuilder::new()
+            .on_http(http_url)
+            .get_block_number()
+            .await
+            .unwrap();
+
+        let (input, posted_at_l1_block) =
+            get_env_input_for_linea_l1_call(LINEA_CHAIN_ID, rpc_url_ethereum(), l1_block).await;
+        let posted = get_linea_l1_posted_block(LINEA_CHAIN_ID, rpc_url_ethereum())
+            .await
+            .unwrap();
+
+        assert!(input.is_some());
+        let posted_at_l1_block = posted_at_l1_block.unwrap();
+        assert!(posted_at_l1_block > 0);
+        assert!(posted >= posted_at_l1_block);
+    }
 }
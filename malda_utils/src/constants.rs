@@ -58,8 +58,20 @@ pub const BASE_SEPOLIA_SEQUENCER: Address = address!("b830b99c95Ea32300039624Cb5
 /// The address of the Linea sequencer contract on the sepolia network.
 pub const LINEA_SEPOLIA_SEQUENCER: Address = address!("a27342f1b74c0cfb2cda74bac1628d0c1a9752f2");
 
-/// The address of the L1Block contract on Optimism.
-/// This contract provides L1 block information to L2.
+/// Sequencer addresses accepted for Optimism commitments.
+///
+/// During a key rotation this holds both the new and the previous sequencer; the previous
+/// one is removed once the rotation window has passed.
+pub const OPTIMISM_SEQUENCERS: &[Address] = &[OPTIMISM_SEQUENCER];
+/// Sequencer addresses accepted for Base commitments.
+pub const BASE_SEQUENCERS: &[Address] = &[BASE_SEQUENCER];
+/// Sequencer addresses accepted for Optimism commitments on the sepolia network.
+pub const OPTIMISM_SEPOLIA_SEQUENCERS: &[Address] = &[OPTIMISM_SEPOLIA_SEQUENCER];
+/// Sequencer addresses accepted for Base commitments on the sepolia network.
+pub const BASE_SEPOLIA_SEQUENCERS: &[Address] = &[BASE_SEPOLIA_SEQUENCER];
+
+/// The standard address of the L1Block predeploy on OpStack chains.
+/// This contract provides L1 block information to L2. Use `l1_block_address` to look it up per chain.
 pub const L1_BLOCK_ADDRESS_OPSTACK: Address = address!("4200000000000000000000000000000000000015");
 /// The address of the MessagePasser contract on Optimism.
 pub const MESSAGE_PASSER_ADDRESS_OPSTACK: Address =
@@ -79,6 +91,26 @@ pub const L1_MESSAGE_SERVICE_LINEA: Address = address!("d19d4B5d358258f05D7B411E
 pub const L1_MESSAGE_SERVICE_LINEA_SEPOLIA: Address =
     address!("B218f8A4Bc926cF1cA7b3423c154a0D627Bdb7E5");
 
+/// The address of the `ScrollChain` rollup contract on Ethereum mainnet.
+///
+/// Scroll has no sequencer commitment API; L2 state is finalized through batches committed
+/// and finalized on this contract (`lastFinalizedBatchIndex`, `finalizedStateRoots`).
+pub const SCROLL_CHAIN: Address = address!("a13BAF47339d63B743e7Da8741db5456DAc1E556");
+/// The address of the `ScrollChain` rollup contract on the sepolia network.
+pub const SCROLL_CHAIN_SEPOLIA: Address = address!("2D567EcE699Eabe5afCd141eDB7A4f2D0D6ce8a0");
+/// The address of the Scroll `L1MessageQueue` contract on Ethereum mainnet.
+pub const L1_MESSAGE_QUEUE_SCROLL: Address = address!("0d7E906BD9cAFa154b048cFa766Cc1E54E39AF9B");
+/// The address of the Scroll `L1MessageQueue` contract on the sepolia network.
+pub const L1_MESSAGE_QUEUE_SCROLL_SEPOLIA: Address =
+    address!("F0B2293F5D834eAe920c6974D50957A1732de763");
+
+/// Upper bound on the size of a decompressed sequencer commitment, in bytes.
+///
+/// Commitments come from external sequencer APIs, so their size is checked before
+/// decompression and SSZ decoding to bound memory use. Well above any OpStack block
+/// that fits the gas limit.
+pub const MAX_SEQUENCER_COMMITMENT_SIZE: usize = 16 * 1024 * 1024;
+
 /// Half of the secp256k1 curve order (n/2).
 ///
 /// This value is used in signature normalization to ensure s values are in the lower half
@@ -103,7 +135,97 @@ pub const REORG_PROTECTION_DEPTH_LINEA_SEPOLIA: u64 = 0;
 pub const REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA: u64 = 0;
 pub const REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA: u64 = 0;
 
+/// Returns true if the get proof data guest accepts queries on this chain.
+pub fn is_supported_chain(chain_id: u64) -> bool {
+    matches!(chain_id, LINEA_CHAIN_ID | BASE_CHAIN_ID | ETHEREUM_CHAIN_ID)
+}
+
+/// Returns the reorg protection depth of a chain.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to look up
+///
+/// # Errors
+/// Returns an error if the chain is not supported
+pub fn reorg_protection_depth(chain_id: u64) -> eyre::Result<u64> {
+    match chain_id {
+        OPTIMISM_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_OPTIMISM),
+        BASE_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_BASE),
+        LINEA_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_LINEA),
+        ETHEREUM_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_ETHEREUM),
+        SCROLL_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_SCROLL),
+        OPTIMISM_SEPOLIA_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA),
+        BASE_SEPOLIA_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_BASE_SEPOLIA),
+        LINEA_SEPOLIA_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_LINEA_SEPOLIA),
+        ETHEREUM_SEPOLIA_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA),
+        SCROLL_SEPOLIA_CHAIN_ID => Ok(REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA),
+        _ => eyre::bail!("invalid chain id: {}", chain_id),
+    }
+}
+
 pub const OPTIMISM_PORTAL: Address = address!("bEb5Fc579115071764c7423A4f12eDde41f106Ed");
 pub const OPTIMISM_SEPOLIA_PORTAL: Address = address!("16Fc5058F25648194471939df75CF27A2fdC48BC");
 pub const BASE_PORTAL: Address = address!("49048044D57e1C92A77f79988d21Fa8fAF74E97e");
 pub const BASE_SEPOLIA_PORTAL: Address = address!("49f53e41452C74589E85cA1677426Ba426459e85");
+
+/// Dispute game types accepted for OpStack L1 inclusion proofs, per chain.
+///
+/// `0` is the permissionless CANNON fault dispute game. Extend these sets when a chain
+/// moves its respected game type.
+pub const ALLOWED_GAME_TYPES_OPTIMISM: &[U256] = &[U256::ZERO];
+pub const ALLOWED_GAME_TYPES_BASE: &[U256] = &[U256::ZERO];
+pub const ALLOWED_GAME_TYPES_OPTIMISM_SEPOLIA: &[U256] = &[U256::ZERO];
+pub const ALLOWED_GAME_TYPES_BASE_SEPOLIA: &[U256] = &[U256::ZERO];
+
+/// Returns the dispute game types accepted for an OpStack chain.
+///
+/// # Panics
+/// Panics if the chain is not an OpStack chain
+pub fn allowed_game_types(chain_id: u64) -> &'static [U256] {
+    match chain_id {
+        OPTIMISM_CHAIN_ID => ALLOWED_GAME_TYPES_OPTIMISM,
+        BASE_CHAIN_ID => ALLOWED_GAME_TYPES_BASE,
+        OPTIMISM_SEPOLIA_CHAIN_ID => ALLOWED_GAME_TYPES_OPTIMISM_SEPOLIA,
+        BASE_SEPOLIA_CHAIN_ID => ALLOWED_GAME_TYPES_BASE_SEPOLIA,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Returns the sequencer addresses whose block commitments are accepted on an OpStack chain.
+///
+/// # Panics
+/// Panics if the chain is not an OpStack chain
+pub fn opstack_sequencers(chain_id: u64) -> &'static [Address] {
+    match chain_id {
+        OPTIMISM_CHAIN_ID => OPTIMISM_SEQUENCERS,
+        BASE_CHAIN_ID => BASE_SEQUENCERS,
+        OPTIMISM_SEPOLIA_CHAIN_ID => OPTIMISM_SEPOLIA_SEQUENCERS,
+        BASE_SEPOLIA_CHAIN_ID => BASE_SEPOLIA_SEQUENCERS,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Returns the address of the L1 message service Linea posts its L2 blocks to.
+///
+/// # Panics
+/// Panics if the chain is not Linea
+pub fn linea_l1_message_service(chain_id: u64) -> Address {
+    match chain_id {
+        LINEA_CHAIN_ID => L1_MESSAGE_SERVICE_LINEA,
+        LINEA_SEPOLIA_CHAIN_ID => L1_MESSAGE_SERVICE_LINEA_SEPOLIA,
+        _ => panic!("invalid chain id"),
+    }
+}
+
+/// Returns the address of the L1Block contract on an OpStack chain.
+///
+/// # Panics
+/// Panics if the chain is not an OpStack chain
+pub fn l1_block_address(chain_id: u64) -> Address {
+    match chain_id {
+        OPTIMISM_CHAIN_ID | BASE_CHAIN_ID | OPTIMISM_SEPOLIA_CHAIN_ID | BASE_SEPOLIA_CHAIN_ID => {
+            L1_BLOCK_ADDRESS_OPSTACK
+        }
+        _ => panic!("invalid chain id"),
+    }
+}
@@ -39,6 +39,9 @@ use k256::ecdsa::{Error, RecoveryId, VerifyingKey};
 /// - A domain separator (currently zero)
 /// - The chain ID in padded format
 /// - The keccak256 hash of the input data
+///
+/// This is the op-node block gossip signing scheme, so the result must stay byte-for-byte
+/// identical to it for sequencer commitments to verify.
 pub fn signature_msg(data: &[u8], chain_id: u64) -> B256 {
     let domain = B256::ZERO;
     let chain_id = B256::left_padding_from(&chain_id.to_be_bytes());
@@ -169,7 +172,8 @@ pub fn signature_from_bytes(signature: &Bytes) -> Signature {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::hex;
+    use crate::constants::{BASE_CHAIN_ID, OPTIMISM_CHAIN_ID};
+    use alloy_primitives::{b256, hex};
     use k256::ecdsa::SigningKey;
 
     #[test]
@@ -191,6 +195,52 @@ mod tests {
         assert_ne!(msg1, msg2);
     }
 
+    #[test]
+    fn test_signature_msg_golden_vectors() {
+        // keccak256(domain (zero) || chain_id (32 bytes, big endian) || keccak256(payload)),
+        // the op-node block gossip signing scheme
+        assert_eq!(
+            signature_msg(b"Hello, World!", OPTIMISM_CHAIN_ID),
+            b256!("185e377bc8f29bef8a060152132651be1653af255283d7c7876069132d3a9369")
+        );
+        assert_eq!(
+            signature_msg(b"Hello, World!", BASE_CHAIN_ID),
+            b256!("ff446ebaff76562cdd7795a78f226c03927754bb5fee71fb09a9e71323c46e01")
+        );
+        assert_eq!(
+            signature_msg(&[], OPTIMISM_CHAIN_ID),
+            b256!("8f0376107ed64d01dcf2878be3dcca11b06ae66209215dbf07b109f516a57ef3")
+        );
+    }
+
+    #[test]
+    fn test_signature_msg_is_bound_to_chain_id() {
+        let signing_key = SigningKey::from_slice(
+            &hex::decode("0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef")
+                .expect("Failed to decode test private key hex string"),
+        )
+        .expect("Failed to create signing key from bytes");
+        let expected_address = Address::from_public_key(signing_key.verifying_key());
+
+        let payload = b"execution payload";
+        let sighash = signature_msg(payload, OPTIMISM_CHAIN_ID);
+        let (sig, recid) = signing_key
+            .sign_prehash_recoverable(sighash.as_slice())
+            .expect("Failed to sign test message");
+
+        let mut sig_bytes = [0u8; 65];
+        sig_bytes[..64].copy_from_slice(&sig.to_bytes());
+        sig_bytes[64] = recid.to_byte();
+        let signature = signature_from_bytes(&sig_bytes.into());
+
+        assert_eq!(recover_signer(signature, sighash), Some(expected_address));
+        // The same signature replayed on another chain recovers a different signer
+        assert_ne!(
+            recover_signer(signature, signature_msg(payload, BASE_CHAIN_ID)),
+            Some(expected_address)
+        );
+    }
+
     #[test]
     fn test_recover_signer() {
         // Test with a known public key and its corresponding address
@@ -33,3 +33,6 @@ pub mod cryptography;
 
 /// L1 validation
 pub mod validators_ethereum_light_client;
+
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -0,0 +1,169 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+//! Synthetic inputs for validator unit tests.
+//!
+//! Builds headers, header chains, and signed sequencer commitments from test keys, so
+//! validators can be exercised without RPC access or the zkVM.
+
+use crate::cryptography::{signature_from_bytes, signature_msg};
+use crate::types::{ExecutionPayload, SequencerCommitment};
+use alloy_consensus::Header;
+use alloy_primitives::{Address, Bytes, PrimitiveSignature as Signature, B256, U256};
+use k256::ecdsa::SigningKey;
+use risc0_steel::serde::RlpHeader;
+use ssz_types::{FixedVector, VariableList};
+
+/// Returns a deterministic signing key derived from `seed`.
+pub fn test_signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_slice(&[seed; 32]).expect("Failed to create test signing key")
+}
+
+/// Returns the address of a signing key.
+pub fn signer_address(signing_key: &SigningKey) -> Address {
+    Address::from_public_key(signing_key.verifying_key())
+}
+
+/// Signs a prehashed message.
+pub fn sign_prehash(signing_key: &SigningKey, sighash: B256) -> Signature {
+    let (sig, recid) = signing_key
+        .sign_prehash_recoverable(sighash.as_slice())
+        .expect("Failed to sign test message");
+    let mut sig_bytes = [0u8; 65];
+    sig_bytes[..64].copy_from_slice(&sig.to_bytes());
+    sig_bytes[64] = recid.to_byte();
+    signature_from_bytes(&sig_bytes.into())
+}
+
+/// Signs a Linea header the way the Linea sequencer does.
+///
+/// The signature covers the header hash with `extra_data` set to the vanity prefix, and is
+/// appended to `extra_data` as `r || s || v`.
+pub fn signed_linea_header(mut header: Header, signing_key: &SigningKey) -> RlpHeader<Header> {
+    let (sig, recid) = signing_key
+        .sign_prehash_recoverable(header.hash_slow().as_slice())
+        .expect("Failed to sign test header");
+    let mut extra_data = header.extra_data.to_vec();
+    extra_data.extend_from_slice(&sig.to_bytes());
+    extra_data.push(recid.to_byte());
+    header.extra_data = extra_data.into();
+    RlpHeader::new(header)
+}
+
+/// Builds a sequencer commitment over `data` signed for `chain_id`.
+pub fn signed_sequencer_commitment(
+    data: Bytes,
+    chain_id: u64,
+    signing_key: &SigningKey,
+) -> SequencerCommitment {
+    let signature = sign_prehash(signing_key, signature_msg(&data, chain_id));
+    SequencerCommitment { data, signature }
+}
+
+/// Builds a sequencer commitment to an otherwise empty execution payload of `block_hash`,
+/// signed for `chain_id`.
+pub fn signed_payload_commitment(
+    block_hash: B256,
+    chain_id: u64,
+    signing_key: &SigningKey,
+) -> SequencerCommitment {
+    let payload = ExecutionPayload {
+        parent_hash: B256::ZERO,
+        fee_recipient: Address::ZERO,
+        state_root: B256::ZERO,
+        receipts_root: B256::ZERO,
+        logs_bloom: FixedVector::from(vec![0; 256]),
+        prev_randao: B256::ZERO,
+        block_number: 1,
+        gas_limit: 0,
+        gas_used: 0,
+        timestamp: 0,
+        extra_data: VariableList::empty(),
+        base_fee_per_gas: U256::ZERO,
+        block_hash,
+        transactions: VariableList::empty(),
+        withdrawals: VariableList::empty(),
+        blob_gas_used: 0,
+        excess_blob_gas: 0,
+        withdrawals_root: B256::ZERO,
+    };
+    // The payload is prefixed by the 32 byte parent beacon block root
+    let mut data = vec![0; 32];
+    data.extend_from_slice(&ssz::Encode::as_ssz_bytes(&payload));
+    signed_sequencer_commitment(data.into(), chain_id, signing_key)
+}
+
+/// Builder for execution headers with controllable fields.
+///
+/// Unset fields keep their `Header::default()` values; `hash_slow()` of the built header
+/// covers every field, so links between built headers are real hash links.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderBuilder {
+    header: Header,
+}
+
+impl HeaderBuilder {
+    /// Creates a builder for a default header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the parent hash.
+    pub fn parent_hash(mut self, parent_hash: B256) -> Self {
+        self.header.parent_hash = parent_hash;
+        self
+    }
+
+    /// Sets the block number.
+    pub fn number(mut self, number: u64) -> Self {
+        self.header.number = number;
+        self
+    }
+
+    /// Sets the extra data, e.g. the vanity prefix of a Linea header before signing.
+    pub fn extra_data(mut self, extra_data: Bytes) -> Self {
+        self.header.extra_data = extra_data;
+        self
+    }
+
+    /// Returns the unwrapped header.
+    pub fn header(self) -> Header {
+        self.header
+    }
+
+    /// Builds the header.
+    pub fn build(self) -> RlpHeader<Header> {
+        RlpHeader::new(self.header)
+    }
+
+    /// Builds `len` hash-linked headers, the first one as configured and every following one
+    /// on top of its predecessor with the next block number.
+    pub fn linked_chain(self, len: u64) -> Vec<RlpHeader<Header>> {
+        let mut header = self.header;
+        (0..len)
+            .map(|_| {
+                let built = RlpHeader::new(header.clone());
+                header.parent_hash = built.hash_slow();
+                header.number += 1;
+                built
+            })
+            .collect()
+    }
+}
+
+/// Builds `len` headers numbered from 1, hash-linked on top of `parent_hash`.
+pub fn linked_headers(parent_hash: B256, len: u64) -> Vec<RlpHeader<Header>> {
+    HeaderBuilder::new()
+        .parent_hash(parent_hash)
+        .number(1)
+        .linked_chain(len)
+}
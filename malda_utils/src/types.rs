@@ -26,8 +26,11 @@ use alloy_rlp::RlpEncodable;
 use ssz_derive::{Decode, Encode};
 use ssz_types::{typenum, FixedVector, VariableList};
 
+use crate::constants::*;
 use crate::cryptography::signature_msg;
+use alloy_encode_packed::{abi, SolidityDataType, TakeLastXBytes};
 use alloy_primitives::{Address, Bytes, PrimitiveSignature as Signature, B256, U256};
+use alloy_sol_types::{SolCall, SolValue};
 
 
 sol! {
@@ -38,7 +41,7 @@ sol! {
         /// # Arguments
         /// * `account` - The address to query the proof data for
         /// * `dstChainId` - The chainId to query the proof data for
-        function getProofData(address account, uint32 dstChainId) external view returns (bytes memory);
+        function getProofData(address account, uint32 dstChainId) external view returns (Amounts memory);
     }
 
     interface IL1MessageService {
@@ -115,11 +118,31 @@ sol! {
         function aggregate3(Call3[] calldata calls) external payable returns (CallResult[] memory results);
     }
 
+    /// Amounts returned by `IMaldaMarket.getProofData`.
     struct Amounts {
         uint256 amountIn;
         uint256 amountOut;
     }
 
+    /// Result of a view call committed by the contract-call guest.
+    ///
+    /// The journal is the ABI encoding of `ContractCallJournalEntry[]`.
+    #[derive(Debug, PartialEq)]
+    struct ContractCallJournalEntry {
+        /// @dev Chain the call was executed on
+        uint64 chainId;
+        /// @dev Called contract
+        address target;
+        /// @dev Calldata, including the selector
+        bytes callData;
+        /// @dev Data returned by the call, decoded by the consumer
+        bytes returnData;
+        /// @dev Whether the state was anchored to L1
+        bool l1Inclusion;
+        /// @dev Number of blocks the validated head sits above the queried state
+        uint16 reorgProtectionDepth;
+    }
+
     /// @title Interface for the Optimism Portal
     interface IOptimismPortal {
         /// @notice Returns the address of the DisputeGameFactory
@@ -155,8 +178,20 @@ impl SequencerCommitment {
     /// # Returns
     /// * `Result<Self>` - The created commitment or an error
     pub fn new(data: &[u8]) -> Result<Self> {
+        let decompressed_len = snap::raw::decompress_len(data)?;
+        if decompressed_len > MAX_SEQUENCER_COMMITMENT_SIZE {
+            eyre::bail!(
+                "commitment of {} bytes exceeds the maximum of {} bytes",
+                decompressed_len,
+                MAX_SEQUENCER_COMMITMENT_SIZE
+            );
+        }
+
         let mut decoder = snap::raw::Decoder::new();
         let decompressed = decoder.decompress_vec(&data)?;
+        if decompressed.len() < 65 {
+            eyre::bail!("commitment shorter than a signature");
+        }
 
         let signature = Signature::try_from(&decompressed[..65])?;
         let data = Bytes::from(decompressed[65..].to_vec());
@@ -183,6 +218,26 @@ impl SequencerCommitment {
 
         Ok(())
     }
+
+    /// Verifies the commitment signature against a set of accepted signers and a chain ID.
+    ///
+    /// # Arguments
+    /// * `signers` - The accepted signers' addresses
+    /// * `chain_id` - The blockchain network ID
+    ///
+    /// # Returns
+    /// * `Result<Address>` - The accepted signer that signed the commitment, Error otherwise
+    pub fn verify_any(&self, signers: &[Address], chain_id: u64) -> Result<Address> {
+        let msg = signature_msg(&self.data, chain_id);
+        let pk = self.signature.recover_from_prehash(&msg)?;
+        let recovered_signer = Address::from_public_key(&pk);
+
+        if !signers.contains(&recovered_signer) {
+            eyre::bail!("invalid signer");
+        }
+
+        Ok(recovered_signer)
+    }
 }
 
 /// Conversion implementation from SequencerCommitment to ExecutionPayload.
@@ -197,7 +252,14 @@ impl TryFrom<&SequencerCommitment> for ExecutionPayload {
     /// # Returns
     /// * `Result<Self>` - The converted payload or an error
     fn try_from(value: &SequencerCommitment) -> Result<Self> {
-        let payload_bytes = &value.data[32..];
+        if value.data.len() > MAX_SEQUENCER_COMMITMENT_SIZE {
+            eyre::bail!("commitment exceeds the maximum size");
+        }
+        // The payload is prefixed by the 32 byte parent beacon block root
+        let payload_bytes = value
+            .data
+            .get(32..)
+            .ok_or_else(|| eyre::eyre!("decode failed"))?;
         ssz::Decode::from_ssz_bytes(payload_bytes).map_err(|_| eyre::eyre!("decode failed"))
     }
 }
@@ -265,3 +327,614 @@ pub struct Withdrawal {
     /// Amount being withdrawn
     amount: u64,
 }
+
+/// Selector of `IMaldaMarket.getProofData(address,uint32)`, derived from the ABI.
+pub const GET_PROOF_DATA_SELECTOR: [u8; 4] = IMaldaMarket::getProofDataCall::SELECTOR;
+
+/// Length in bytes of a packed proof data journal entry.
+pub const PROOF_DATA_JOURNAL_ENTRY_LEN: usize = 115;
+
+/// A single proof data result as committed to the journal by the get-proof-data guest.
+///
+/// Each entry is `abi.encodePacked(address user, address market, uint256 amountIn,
+/// uint256 amountOut, uint32 chainId, uint32 targetChainId, bool l1Inclusion,
+/// uint16 reorgProtectionDepth)` and the journal is the ABI encoding of `bytes[]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofDataJournalEntry {
+    /// The queried user
+    pub user: Address,
+    /// The queried market
+    pub market: Address,
+    /// Amount in as returned by `getProofData`
+    pub amount_in: U256,
+    /// Amount out as returned by `getProofData`
+    pub amount_out: U256,
+    /// Chain the proof data was read from
+    pub chain_id: u64,
+    /// Destination chain passed to `getProofData`
+    pub target_chain_id: u64,
+    /// Whether the state was anchored to L1
+    pub l1_inclusion: bool,
+    /// Number of blocks the validated head sits above the queried state
+    pub reorg_protection_depth: u16,
+}
+
+impl ProofDataJournalEntry {
+    /// Encodes the entry in its packed journal layout.
+    pub fn encode_packed(&self) -> Bytes {
+        let input = vec![
+            SolidityDataType::Address(self.user),
+            SolidityDataType::Address(self.market),
+            SolidityDataType::Number(self.amount_in),
+            SolidityDataType::Number(self.amount_out),
+            SolidityDataType::NumberWithShift(U256::from(self.chain_id), TakeLastXBytes(32)),
+            SolidityDataType::NumberWithShift(U256::from(self.target_chain_id), TakeLastXBytes(32)),
+            SolidityDataType::Bool(self.l1_inclusion),
+            SolidityDataType::NumberWithShift(
+                U256::from(self.reorg_protection_depth),
+                TakeLastXBytes(16),
+            ),
+        ];
+
+        let (bytes, _hash) = abi::encode_packed(&input);
+        bytes.into()
+    }
+
+    /// Decodes an entry from its packed journal layout.
+    ///
+    /// # Arguments
+    /// * `data` - The packed entry bytes
+    ///
+    /// # Returns
+    /// * `Result<Self>` - The decoded entry or an error if the length is wrong
+    pub fn decode_packed(data: &[u8]) -> Result<Self> {
+        if data.len() != PROOF_DATA_JOURNAL_ENTRY_LEN {
+            eyre::bail!(
+                "invalid journal entry length: expected {}, got {}",
+                PROOF_DATA_JOURNAL_ENTRY_LEN,
+                data.len()
+            );
+        }
+
+        let u32_at = |offset: usize| {
+            u32::from_be_bytes(
+                data[offset..offset + 4]
+                    .try_into()
+                    .expect("slice of 4 bytes"),
+            )
+        };
+
+        Ok(Self {
+            user: Address::from_slice(&data[0..20]),
+            market: Address::from_slice(&data[20..40]),
+            amount_in: U256::from_be_slice(&data[40..72]),
+            amount_out: U256::from_be_slice(&data[72..104]),
+            chain_id: u32_at(104) as u64,
+            target_chain_id: u32_at(108) as u64,
+            l1_inclusion: data[112] != 0,
+            reorg_protection_depth: u16::from_be_bytes([data[113], data[114]]),
+        })
+    }
+
+    /// Decodes all entries of a get-proof-data journal.
+    ///
+    /// # Arguments
+    /// * `journal` - The ABI encoded `bytes[]` journal
+    ///
+    /// # Returns
+    /// * `Result<Vec<Self>>` - The decoded entries or an error
+    pub fn decode_journal(journal: &[u8]) -> Result<Vec<Self>> {
+        let entries = <Vec<Bytes>>::abi_decode(journal, true)
+            .map_err(|e| eyre::eyre!("journal decode failed: {}", e))?;
+        entries
+            .iter()
+            .map(|entry| Self::decode_packed(entry))
+            .collect()
+    }
+}
+
+/// A view call on an arbitrary contract, as read by the contract-call guest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractCall {
+    /// Contract to call
+    pub target: Address,
+    /// ABI encoded calldata, including the selector
+    pub call_data: Bytes,
+}
+
+impl From<ContractCall> for Call3 {
+    /// Converts the call into a multicall entry that reverts the whole batch on failure.
+    fn from(call: ContractCall) -> Self {
+        Call3 {
+            target: call.target,
+            allowFailure: false,
+            callData: call.call_data,
+        }
+    }
+}
+
+/// Ethereum network a chain belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Network {
+    /// Ethereum mainnet and its rollups
+    Mainnet,
+    /// Sepolia testnet and its rollups
+    Sepolia,
+}
+
+impl Network {
+    /// Returns the network a chain belongs to.
+    ///
+    /// # Arguments
+    /// * `chain_id` - The chain ID to classify
+    ///
+    /// # Panics
+    /// Panics if the chain ID is not supported
+    pub fn from_chain_id(chain_id: u64) -> Self {
+        Chain::from_id(chain_id)
+            .expect("invalid chain id")
+            .network()
+    }
+
+    /// Returns true for the Sepolia testnet.
+    pub fn is_sepolia(&self) -> bool {
+        *self == Network::Sepolia
+    }
+}
+
+/// Chain known to the protocol, a typed alternative to comparing raw chain IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Chain {
+    Ethereum,
+    Optimism,
+    Linea,
+    Scroll,
+    Base,
+    EthereumSepolia,
+    OptimismSepolia,
+    LineaSepolia,
+    ScrollSepolia,
+    BaseSepolia,
+}
+
+impl Chain {
+    /// Returns the chain with the given ID.
+    ///
+    /// # Arguments
+    /// * `chain_id` - The chain ID to look up
+    ///
+    /// # Returns
+    /// * `Option<Self>` - The chain, or `None` if the ID is unknown
+    pub fn from_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            ETHEREUM_CHAIN_ID => Some(Chain::Ethereum),
+            OPTIMISM_CHAIN_ID => Some(Chain::Optimism),
+            LINEA_CHAIN_ID => Some(Chain::Linea),
+            SCROLL_CHAIN_ID => Some(Chain::Scroll),
+            BASE_CHAIN_ID => Some(Chain::Base),
+            ETHEREUM_SEPOLIA_CHAIN_ID => Some(Chain::EthereumSepolia),
+            OPTIMISM_SEPOLIA_CHAIN_ID => Some(Chain::OptimismSepolia),
+            LINEA_SEPOLIA_CHAIN_ID => Some(Chain::LineaSepolia),
+            SCROLL_SEPOLIA_CHAIN_ID => Some(Chain::ScrollSepolia),
+            BASE_SEPOLIA_CHAIN_ID => Some(Chain::BaseSepolia),
+            _ => None,
+        }
+    }
+
+    /// Returns the chain ID.
+    pub fn id(&self) -> u64 {
+        match self {
+            Chain::Ethereum => ETHEREUM_CHAIN_ID,
+            Chain::Optimism => OPTIMISM_CHAIN_ID,
+            Chain::Linea => LINEA_CHAIN_ID,
+            Chain::Scroll => SCROLL_CHAIN_ID,
+            Chain::Base => BASE_CHAIN_ID,
+            Chain::EthereumSepolia => ETHEREUM_SEPOLIA_CHAIN_ID,
+            Chain::OptimismSepolia => OPTIMISM_SEPOLIA_CHAIN_ID,
+            Chain::LineaSepolia => LINEA_SEPOLIA_CHAIN_ID,
+            Chain::ScrollSepolia => SCROLL_SEPOLIA_CHAIN_ID,
+            Chain::BaseSepolia => BASE_SEPOLIA_CHAIN_ID,
+        }
+    }
+
+    /// Returns true for Ethereum L1.
+    pub fn is_ethereum(&self) -> bool {
+        matches!(self, Chain::Ethereum | Chain::EthereumSepolia)
+    }
+
+    /// Returns true for OpStack chains, i.e. Optimism and Base.
+    pub fn is_opstack(&self) -> bool {
+        matches!(
+            self,
+            Chain::Optimism | Chain::Base | Chain::OptimismSepolia | Chain::BaseSepolia
+        )
+    }
+
+    /// Returns true for Linea.
+    pub fn is_linea(&self) -> bool {
+        matches!(self, Chain::Linea | Chain::LineaSepolia)
+    }
+
+    /// Returns true for Scroll.
+    pub fn is_scroll(&self) -> bool {
+        matches!(self, Chain::Scroll | Chain::ScrollSepolia)
+    }
+
+    /// Returns true for chains on the Sepolia testnet.
+    pub fn is_sepolia(&self) -> bool {
+        self.network().is_sepolia()
+    }
+
+    /// Returns the network the chain belongs to.
+    pub fn network(&self) -> Network {
+        match self {
+            Chain::Ethereum | Chain::Optimism | Chain::Linea | Chain::Scroll | Chain::Base => {
+                Network::Mainnet
+            }
+            Chain::EthereumSepolia
+            | Chain::OptimismSepolia
+            | Chain::LineaSepolia
+            | Chain::ScrollSepolia
+            | Chain::BaseSepolia => Network::Sepolia,
+        }
+    }
+
+    /// Returns the Ethereum L1 of the chain's network.
+    pub fn l1(&self) -> Chain {
+        match self.network() {
+            Network::Mainnet => Chain::Ethereum,
+            Network::Sepolia => Chain::EthereumSepolia,
+        }
+    }
+
+    /// Returns the number of blocks below the head that are considered safe from reorgs.
+    pub fn reorg_depth(&self) -> u64 {
+        match self {
+            Chain::Ethereum => REORG_PROTECTION_DEPTH_ETHEREUM,
+            Chain::Optimism => REORG_PROTECTION_DEPTH_OPTIMISM,
+            Chain::Linea => REORG_PROTECTION_DEPTH_LINEA,
+            Chain::Scroll => REORG_PROTECTION_DEPTH_SCROLL,
+            Chain::Base => REORG_PROTECTION_DEPTH_BASE,
+            Chain::EthereumSepolia => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
+            Chain::OptimismSepolia => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
+            Chain::LineaSepolia => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
+            Chain::ScrollSepolia => REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
+            Chain::BaseSepolia => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
+        }
+    }
+}
+
+/// Lane a proof is generated for, trading latency for finality.
+///
+/// The fast lane proves state at the L2 head attested by the sequencer's signed commitment.
+/// It is available within seconds, but trusts the sequencer not to equivocate until the block
+/// is posted to L1. The slow lane additionally proves that the L2 state is included in
+/// Ethereum (via the dispute game on OpStack chains and the rollup contract on Linea), so it
+/// only relies on L1 finality but lags the L2 head by the L1 settlement delay.
+///
+/// `bool` converts into a lane for compatibility with the former `l1_inclusion` flag,
+/// `true` selecting the slow lane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProofLane {
+    /// Sequencer commitment only, low latency
+    #[default]
+    Fast,
+    /// Sequencer commitment plus L1 inclusion, L1 finality
+    Slow,
+}
+
+impl ProofLane {
+    /// Returns true if proofs on this lane include L1 inclusion data.
+    pub fn l1_inclusion(&self) -> bool {
+        *self == ProofLane::Slow
+    }
+}
+
+impl From<bool> for ProofLane {
+    fn from(l1_inclusion: bool) -> Self {
+        if l1_inclusion {
+            ProofLane::Slow
+        } else {
+            ProofLane::Fast
+        }
+    }
+}
+
+/// Block a proof anchors to, a finer latency/security dial than `ProofLane`.
+///
+/// `Unsafe` and `Finalized` are the fast and slow lanes. `Safe` stays on the fast lane but
+/// queries the chain's `safe` block instead of the reorg protected one, hash-linked to the
+/// sequencer-attested head. Its safety is as reported by the RPC node, not proven in the guest.
+///
+/// `ProofLane` and `bool` convert into the matching `Unsafe` or `Finalized` safety.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockSafety {
+    /// Sequencer-attested head, reorg protected
+    #[default]
+    Unsafe,
+    /// Safe block, linked to the sequencer-attested head
+    Safe,
+    /// Block included in Ethereum
+    Finalized,
+}
+
+impl BlockSafety {
+    /// Returns the lane proofs anchored to this block are generated on.
+    pub fn lane(&self) -> ProofLane {
+        match self {
+            BlockSafety::Unsafe | BlockSafety::Safe => ProofLane::Fast,
+            BlockSafety::Finalized => ProofLane::Slow,
+        }
+    }
+}
+
+impl From<ProofLane> for BlockSafety {
+    fn from(lane: ProofLane) -> Self {
+        match lane {
+            ProofLane::Fast => BlockSafety::Unsafe,
+            ProofLane::Slow => BlockSafety::Finalized,
+        }
+    }
+}
+
+impl From<bool> for BlockSafety {
+    fn from(l1_inclusion: bool) -> Self {
+        ProofLane::from(l1_inclusion).into()
+    }
+}
+
+/// Market method a proof data journal entry is submitted to.
+///
+/// Each method settles against one of the journal's accumulated amounts, so adding a
+/// method requires choosing the amount it is checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExternalMethod {
+    /// `mintExternal`, settled against `amountIn`
+    MintExternal,
+    /// `repayExternal`, settled against `amountOut`
+    RepayExternal,
+    /// `outHere`, settled against `amountOut`
+    OutHere,
+}
+
+impl ExternalMethod {
+    /// Returns the journal amount the method is settled against.
+    ///
+    /// # Arguments
+    /// * `entry` - The journal entry the method is submitted with
+    pub fn journal_amount(&self, entry: &ProofDataJournalEntry) -> U256 {
+        match self {
+            ExternalMethod::MintExternal => entry.amount_in,
+            ExternalMethod::RepayExternal | ExternalMethod::OutHere => entry.amount_out,
+        }
+    }
+
+    /// Checks that an event amount is covered by the journal amount of this method.
+    ///
+    /// # Arguments
+    /// * `entry` - The journal entry the method is submitted with
+    /// * `amount` - The amount requested by the event
+    ///
+    /// # Returns
+    /// * `Result<()>` - An error if the amount exceeds the journal amount
+    pub fn validate_amount(&self, entry: &ProofDataJournalEntry, amount: U256) -> Result<()> {
+        let journal_amount = self.journal_amount(entry);
+        if amount > journal_amount {
+            eyre::bail!(
+                "{:?} amount {} exceeds journal amount {}",
+                self,
+                amount,
+                journal_amount
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cryptography::signature_from_bytes;
+    use alloy_primitives::address;
+    use k256::ecdsa::SigningKey;
+
+    #[test]
+    fn test_execution_payload_from_short_commitment_fails() {
+        let commitment = SequencerCommitment {
+            data: Bytes::from(vec![0u8; 31]),
+            signature: Signature::new(U256::from(1), U256::from(1), false),
+        };
+
+        let err = ExecutionPayload::try_from(&commitment).unwrap_err();
+        assert_eq!(err.to_string(), "decode failed");
+    }
+
+    #[test]
+    fn test_oversized_sequencer_commitment_is_rejected() {
+        let oversized = vec![0u8; MAX_SEQUENCER_COMMITMENT_SIZE + 1];
+        let compressed = snap::raw::Encoder::new().compress_vec(&oversized).unwrap();
+
+        let err = SequencerCommitment::new(&compressed).unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum"));
+
+        let commitment = SequencerCommitment {
+            data: Bytes::from(oversized),
+            signature: Signature::new(U256::from(1), U256::from(1), false),
+        };
+        assert!(ExecutionPayload::try_from(&commitment).is_err());
+    }
+
+    #[test]
+    fn test_sequencer_commitment_verify_any() {
+        let signing_key = SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let signer = Address::from_public_key(signing_key.verifying_key());
+        let previous_signer = address!("AAAA45d9549EDA09E70937013520214382Ffc4A2");
+
+        let data = Bytes::from(vec![0xab; 64]);
+        let msg = signature_msg(&data, OPTIMISM_CHAIN_ID);
+        let (sig, recid) = signing_key
+            .sign_prehash_recoverable(msg.as_slice())
+            .unwrap();
+        let mut sig_bytes = [0u8; 65];
+        sig_bytes[..64].copy_from_slice(&sig.to_bytes());
+        sig_bytes[64] = recid.to_byte();
+        let commitment = SequencerCommitment {
+            data,
+            signature: signature_from_bytes(&sig_bytes.into()),
+        };
+
+        assert_eq!(
+            commitment
+                .verify_any(&[previous_signer, signer], OPTIMISM_CHAIN_ID)
+                .unwrap(),
+            signer
+        );
+        assert!(commitment
+            .verify_any(&[previous_signer], OPTIMISM_CHAIN_ID)
+            .is_err());
+        assert!(commitment.verify_any(&[signer], BASE_CHAIN_ID).is_err());
+    }
+
+    #[test]
+    fn test_external_method_amount_semantics() {
+        let entry = ProofDataJournalEntry {
+            user: address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3"),
+            market: address!("C7Bc6bD45Eb84D594f51cED3c5497E6812C7732f"),
+            amount_in: U256::from(100),
+            amount_out: U256::from(40),
+            chain_id: LINEA_CHAIN_ID,
+            target_chain_id: BASE_CHAIN_ID,
+            l1_inclusion: false,
+            reorg_protection_depth: 0,
+        };
+
+        assert_eq!(
+            ExternalMethod::MintExternal.journal_amount(&entry),
+            U256::from(100)
+        );
+        assert_eq!(
+            ExternalMethod::RepayExternal.journal_amount(&entry),
+            U256::from(40)
+        );
+        assert!(ExternalMethod::MintExternal
+            .validate_amount(&entry, U256::from(100))
+            .is_ok());
+        assert!(ExternalMethod::RepayExternal
+            .validate_amount(&entry, U256::from(41))
+            .is_err());
+        assert!(ExternalMethod::OutHere
+            .validate_amount(&entry, U256::from(40))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_get_proof_data_selector() {
+        assert_eq!(
+            GET_PROOF_DATA_SELECTOR[..],
+            alloy_primitives::keccak256("getProofData(address,uint32)")[..4]
+        );
+        assert_eq!(GET_PROOF_DATA_SELECTOR, [0x07, 0xd9, 0x23, 0xe9]);
+    }
+
+    #[test]
+    fn test_network_from_chain_id() {
+        assert_eq!(Network::from_chain_id(LINEA_CHAIN_ID), Network::Mainnet);
+        assert_eq!(Network::from_chain_id(ETHEREUM_CHAIN_ID), Network::Mainnet);
+        assert_eq!(
+            Network::from_chain_id(OPTIMISM_SEPOLIA_CHAIN_ID),
+            Network::Sepolia
+        );
+        assert!(Network::from_chain_id(BASE_SEPOLIA_CHAIN_ID).is_sepolia());
+    }
+
+    #[test]
+    fn test_chain_from_id() {
+        for chain_id in [
+            ETHEREUM_CHAIN_ID,
+            OPTIMISM_CHAIN_ID,
+            LINEA_CHAIN_ID,
+            SCROLL_CHAIN_ID,
+            BASE_CHAIN_ID,
+            ETHEREUM_SEPOLIA_CHAIN_ID,
+            OPTIMISM_SEPOLIA_CHAIN_ID,
+            LINEA_SEPOLIA_CHAIN_ID,
+            SCROLL_SEPOLIA_CHAIN_ID,
+            BASE_SEPOLIA_CHAIN_ID,
+        ] {
+            let chain = Chain::from_id(chain_id).unwrap();
+            assert_eq!(chain.id(), chain_id);
+            assert_eq!(
+                chain.reorg_depth(),
+                reorg_protection_depth(chain_id).unwrap()
+            );
+        }
+        assert_eq!(Chain::from_id(42), None);
+
+        assert!(Chain::Base.is_opstack());
+        assert!(Chain::OptimismSepolia.is_opstack());
+        assert!(!Chain::Linea.is_opstack());
+        assert!(!Chain::Scroll.is_opstack());
+        assert!(Chain::LineaSepolia.is_sepolia());
+        assert_eq!(Chain::BaseSepolia.l1(), Chain::EthereumSepolia);
+        assert_eq!(Chain::Scroll.network(), Network::Mainnet);
+    }
+
+    #[test]
+    fn test_proof_lane_from_l1_inclusion() {
+        assert_eq!(ProofLane::from(true), ProofLane::Slow);
+        assert_eq!(ProofLane::from(false), ProofLane::Fast);
+        assert!(ProofLane::Slow.l1_inclusion());
+        assert!(!ProofLane::Fast.l1_inclusion());
+    }
+
+    #[test]
+    fn test_block_safety_lane() {
+        assert_eq!(BlockSafety::from(true), BlockSafety::Finalized);
+        assert_eq!(BlockSafety::from(ProofLane::Fast), BlockSafety::Unsafe);
+        assert_eq!(BlockSafety::Safe.lane(), ProofLane::Fast);
+        assert_eq!(BlockSafety::Finalized.lane(), ProofLane::Slow);
+    }
+
+    #[test]
+    fn test_proof_data_journal_entry_roundtrip() {
+        let entry = ProofDataJournalEntry {
+            user: address!("Ad7f33984bed10518012013D4aB0458D37FEE6F3"),
+            market: address!("B84644c24B4D0823A0770ED698f7C20B88Bcf824"),
+            amount_in: U256::from(1_000_000u64),
+            amount_out: U256::from(42u64),
+            chain_id: 59141,
+            target_chain_id: 11155420,
+            l1_inclusion: true,
+            reorg_protection_depth: 2,
+        };
+
+        let packed = entry.encode_packed();
+        assert_eq!(packed.len(), PROOF_DATA_JOURNAL_ENTRY_LEN);
+        assert_eq!(
+            ProofDataJournalEntry::decode_packed(&packed).unwrap(),
+            entry
+        );
+
+        let journal = vec![packed.clone(), packed].abi_encode();
+        let decoded = ProofDataJournalEntry::decode_journal(&journal).unwrap();
+        assert_eq!(decoded, vec![entry.clone(), entry]);
+    }
+
+    #[test]
+    fn test_contract_call_to_call3() {
+        let call = ContractCall {
+            target: address!("B84644c24B4D0823A0770ED698f7C20B88Bcf824"),
+            call_data: GET_PROOF_DATA_SELECTOR.to_vec().into(),
+        };
+
+        let call3 = Call3::from(call.clone());
+        assert_eq!(call3.target, call.target);
+        assert_eq!(call3.callData, call.call_data);
+        assert!(!call3.allowFailure);
+    }
+
+    #[test]
+    fn test_proof_data_journal_entry_rejects_wrong_length() {
+        assert!(ProofDataJournalEntry::decode_packed(&[0u8; 113]).is_err());
+    }
+}
@@ -31,7 +31,6 @@ use crate::constants::*;
 use crate::cryptography::{recover_signer, signature_from_bytes};
 use crate::types::*;
 use alloy_consensus::Header;
-use alloy_encode_packed::{abi, SolidityDataType, TakeLastXBytes};
 use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_sol_types::SolValue;
 use risc0_op_steel::optimism::OpBlockHeader;
@@ -62,6 +61,7 @@ use risc0_steel::{ethereum::EthEvmInput, serde::RlpHeader, Commitment, Contract,
 /// * Environment validation fails
 /// * Chain length is insufficient
 /// * Block hashes don't match
+/// * Linking block numbers don't follow the environment block
 /// * Multicall execution fails
 /// * Return data decoding fails
 pub fn validate_get_proof_data_call(
@@ -78,6 +78,163 @@ pub fn validate_get_proof_data_call(
     env_input_opstack_for_viewcall_with_l1_inclusion: Option<OpEvmInput>,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+) {
+    let (
+        env_for_viewcall,
+        op_env_for_viewcall_with_l1_inclusion,
+        validate_l1_inclusion,
+        reorg_protection_depth,
+    ) = validate_view_call_env(
+        chain_id,
+        env_input_for_viewcall,
+        sequencer_commitment_opstack,
+        env_input_opstack_for_l1_block_call,
+        linking_blocks,
+        env_input_eth_for_l1_inclusion,
+        env_input_opstack_for_viewcall_with_l1_inclusion,
+        sequencer_commitment_opstack_2,
+        env_input_opstack_for_l1_block_call_2,
+    );
+
+    if op_env_for_viewcall_with_l1_inclusion.is_some() {
+        batch_call_get_proof_data(
+            chain_id,
+            account,
+            asset,
+            target_chain_ids,
+            op_env_for_viewcall_with_l1_inclusion.unwrap(),
+            validate_l1_inclusion,
+            reorg_protection_depth,
+            output,
+        )
+    } else {
+        batch_call_get_proof_data(
+            chain_id,
+            account,
+            asset,
+            target_chain_ids,
+            env_for_viewcall,
+            validate_l1_inclusion,
+            reorg_protection_depth,
+            output,
+        );
+    }
+}
+
+/// Validates and executes view calls on arbitrary contracts using multicall.
+///
+/// Generalizes `validate_get_proof_data_call` to any calldata: the environment is validated
+/// the same way, and every call's raw return data is committed for the consumer to decode.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to validate against
+/// * `calls` - Contract calls to execute
+/// * `env_input_for_viewcall` - Optional EVM environment input for the chain
+/// * `sequencer_commitment_opstack` - Optional sequencer commitment for L2 chains
+/// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input for L1 validation
+/// * `linking_blocks` - Vector of blocks for reorg protection
+/// * `output` - Output vector for the call results
+/// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input for L1 inclusion
+/// * `env_input_opstack_for_viewcall_with_l1_inclusion` - Optional OpStack environment input for L1 inclusion
+/// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment for L2 chains
+/// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input for L1 validation
+///
+/// # Panics
+/// Panics if:
+/// * The environment validation fails, see `validate_view_call_env`
+/// * Any of the calls reverts
+pub fn validate_contract_call(
+    chain_id: u64,
+    calls: Vec<ContractCall>,
+    env_input_for_viewcall: Option<EthEvmInput>,
+    sequencer_commitment_opstack: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
+    linking_blocks: &Vec<RlpHeader<Header>>,
+    output: &mut Vec<ContractCallJournalEntry>,
+    env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
+    env_input_opstack_for_viewcall_with_l1_inclusion: Option<OpEvmInput>,
+    sequencer_commitment_opstack_2: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+) {
+    let (
+        env_for_viewcall,
+        op_env_for_viewcall_with_l1_inclusion,
+        validate_l1_inclusion,
+        reorg_protection_depth,
+    ) = validate_view_call_env(
+        chain_id,
+        env_input_for_viewcall,
+        sequencer_commitment_opstack,
+        env_input_opstack_for_l1_block_call,
+        linking_blocks,
+        env_input_eth_for_l1_inclusion,
+        env_input_opstack_for_viewcall_with_l1_inclusion,
+        sequencer_commitment_opstack_2,
+        env_input_opstack_for_l1_block_call_2,
+    );
+
+    match op_env_for_viewcall_with_l1_inclusion {
+        Some(op_env) => batch_contract_call(
+            chain_id,
+            calls,
+            op_env,
+            validate_l1_inclusion,
+            reorg_protection_depth,
+            output,
+        ),
+        None => batch_contract_call(
+            chain_id,
+            calls,
+            env_for_viewcall,
+            validate_l1_inclusion,
+            reorg_protection_depth,
+            output,
+        ),
+    }
+}
+
+/// Validates the environment for view calls against the chain's sequencer or L1.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to validate against
+/// * `env_input_for_viewcall` - Optional EVM environment input for the chain
+/// * `sequencer_commitment_opstack` - Optional sequencer commitment for L2 chains
+/// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input for L1 validation
+/// * `linking_blocks` - Vector of blocks for reorg protection
+/// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input for L1 inclusion
+/// * `env_input_opstack_for_viewcall_with_l1_inclusion` - Optional OpStack environment input for L1 inclusion
+/// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment for L2 chains
+/// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input for L1 validation
+///
+/// # Returns
+/// Returns a tuple containing:
+/// * `EvmEnv` - The validated EVM environment
+/// * `Option<EvmEnv>` - The OpStack environment to call instead, for OpStack L1 inclusion
+/// * `bool` - Whether L1 inclusion was validated
+/// * `u16` - Number of linking blocks validated above the queried state
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is invalid
+/// * Environment validation fails
+/// * Chain length is insufficient
+/// * Block hashes don't match
+/// * Linking block numbers don't follow the environment block
+pub fn validate_view_call_env(
+    chain_id: u64,
+    env_input_for_viewcall: Option<EthEvmInput>,
+    sequencer_commitment_opstack: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
+    linking_blocks: &Vec<RlpHeader<Header>>,
+    env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
+    env_input_opstack_for_viewcall_with_l1_inclusion: Option<OpEvmInput>,
+    sequencer_commitment_opstack_2: Option<SequencerCommitment>,
+    env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+) -> (
+    EvmEnv<StateDb, RlpHeader<Header>, Commitment>,
+    Option<EvmEnv<StateDb, OpBlockHeader, Commitment>>,
+    bool,
+    u16,
 ) {
     let (
         env_for_viewcall,
@@ -96,6 +253,15 @@ pub fn validate_get_proof_data_call(
         env_input_opstack_for_viewcall_with_l1_inclusion,
     );
 
+    // Hash every linking block once; without linking blocks the block to validate is the env
+    // header, whose hash steel already sealed.
+    let linking_block_hashes = hash_linking_blocks(linking_blocks);
+    let block_hash_to_validate = linking_block_hashes
+        .last()
+        .copied()
+        .unwrap_or(env_header_hash_to_validate);
+
+    let env_block_number = env_header_to_validate.number;
     let validated_block_hash = get_validated_block_hash(
         chain_id,
         env_header_to_validate,
@@ -103,40 +269,31 @@ pub fn validate_get_proof_data_call(
         env_input_opstack_for_l1_block_call,
         env_input_eth_for_l1_inclusion,
         block_header_to_validate,
+        block_hash_to_validate,
         validate_l1_inclusion,
         op_env_commitment.as_ref(),
         sequencer_commitment_opstack_2,
         env_input_opstack_for_l1_block_call_2,
     );
 
-    validate_chain_length(
+    validate_chain_length_with_hashes(
         chain_id_for_length_validation,
         env_header_hash_to_validate,
         linking_blocks,
+        &linking_block_hashes,
         validated_block_hash,
     );
+    validate_chain_numbers(env_block_number, linking_blocks);
 
-    if op_env_for_viewcall_with_l1_inclusion.is_some() {
-        batch_call_get_proof_data(
-            chain_id,
-            account,
-            asset,
-            target_chain_ids,
-            op_env_for_viewcall_with_l1_inclusion.unwrap(),
-            validate_l1_inclusion,
-            output,
-        )
-    } else {
-        batch_call_get_proof_data(
-            chain_id,
-            account,
-            asset,
-            target_chain_ids,
-            env_for_viewcall,
-            validate_l1_inclusion,
-            output,
-        );
-    }
+    let reorg_protection_depth =
+        u16::try_from(linking_blocks.len()).expect("too many linking blocks");
+
+    (
+        env_for_viewcall,
+        op_env_for_viewcall_with_l1_inclusion,
+        validate_l1_inclusion,
+        reorg_protection_depth,
+    )
 }
 
 /// Sorts and verifies relevant parameters for proof data validation.
@@ -228,6 +385,9 @@ pub fn sort_and_verify_relevant_params(
         )
     };
 
+    // Steel v2.0.2 commitments carry no chain identity: `configID` is only the digest of a
+    // chain spec set with `with_chain_spec`, which these envs don't use. The env is bound to
+    // `chain_id` by the chain-specific sequencer and L1 checks on its header hash instead.
     let block_header_to_validate = if linking_blocks.is_empty() {
         env_for_viewcall.header().inner().clone()
     } else {
@@ -298,7 +458,10 @@ pub fn validate_opstack_dispute_game_commitment(
     let returns = contract.call_builder(&game_call).call();
 
     let game_type = returns._0;
-    assert_eq!(game_type, U256::from(0), "game type not respected game");
+    assert!(
+        allowed_game_types(chain_id).contains(&game_type),
+        "game type not respected game"
+    );
 
     let created_at = returns._1;
     let game_address = returns._2;
@@ -363,6 +526,7 @@ pub fn validate_opstack_dispute_game_commitment(
 /// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input for L1 validation
 /// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input for L1 inclusion validation
 /// * `block_header_to_validate` - Last block in the chain for hash validation
+/// * `block_hash_to_validate` - Hash of `block_header_to_validate`
 /// * `validate_l1_inclusion` - Whether to validate L1 inclusion
 /// * `op_env_commitment` - Optional storage hash for L1 inclusion validation
 /// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment for L2 chains
@@ -382,6 +546,7 @@ pub fn get_validated_block_hash(
     env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
     env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
     block_header_to_validate: RlpHeader<Header>,
+    block_hash_to_validate: B256,
     validate_l1_inclusion: bool,
     op_env_commitment: Option<&Commitment>,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
@@ -395,6 +560,7 @@ pub fn get_validated_block_hash(
             env_input_opstack_for_l1_block_call,
             env_input_eth_for_l1_inclusion,
             block_header_to_validate,
+            block_hash_to_validate,
             validate_l1_inclusion,
             sequencer_commitment_opstack_2,
             env_input_opstack_for_l1_block_call_2,
@@ -409,7 +575,7 @@ pub fn get_validated_block_hash(
             sequencer_commitment_opstack,
             env_input_opstack_for_l1_block_call,
             env_input_eth_for_l1_inclusion,
-            block_header_to_validate,
+            block_hash_to_validate,
             validate_l1_inclusion,
             op_env_commitment,
             sequencer_commitment_opstack_2,
@@ -435,7 +601,7 @@ pub fn get_validated_block_hash(
 /// * `sequencer_commitment` - Optional sequencer commitment
 /// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input
 /// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input
-/// * `block_header_to_validate` - Last block for hash validation
+/// * `block_hash_to_validate` - Hash of the last block for hash validation
 /// * `validate_l1_inclusion` - Whether to validate L1 inclusion
 /// * `op_env_commitment` - Optional storage hash for L1 validation
 /// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment
@@ -453,13 +619,13 @@ pub fn get_validated_block_hash_opstack(
     sequencer_commitment: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
     env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
-    block_header_to_validate: RlpHeader<Header>,
+    block_hash_to_validate: B256,
     validate_l1_inclusion: bool,
     op_env_commitment: Option<&Commitment>,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
 ) -> B256 {
-    let validated_hash = block_header_to_validate.hash_slow();
+    let validated_hash = block_hash_to_validate;
     if validate_l1_inclusion {
         let ethereum_chain_id = match chain_id {
             OPTIMISM_CHAIN_ID | BASE_CHAIN_ID => ETHEREUM_CHAIN_ID,
@@ -476,14 +642,18 @@ pub fn get_validated_block_hash_opstack(
         );
 
         assert_eq!(ethereum_hash, validated_hash, "hash mismatch  opstack");
+
+        // The dispute game checks read L1 state from this env. It is the view call env of
+        // `validate_view_call_env`, which links its hash to `ethereum_hash` through the
+        // linking blocks in `validate_chain_length_with_hashes`.
         validate_opstack_dispute_game_commitment(
             chain_id,
             env_input_eth_for_l1_inclusion
                 .as_ref()
-                .unwrap()
+                .expect("env_input_eth_for_l1_inclusion is None")
                 .clone()
                 .into_env(),
-            op_env_commitment.unwrap(),
+            op_env_commitment.expect("op_env_commitment is None"),
         )
     } else {
         validate_opstack_env(chain_id, &sequencer_commitment.unwrap(), validated_hash);
@@ -500,6 +670,7 @@ pub fn get_validated_block_hash_opstack(
 /// * `env_input_opstack_for_l1_block_call` - Optional Optimism environment input
 /// * `env_input_eth_for_l1_inclusion` - Optional Ethereum environment input
 /// * `block_header_to_validate` - Last block for hash validation
+/// * `block_hash_to_validate` - Hash of `block_header_to_validate`
 /// * `validate_l1_inclusion` - Whether to validate L1 inclusion
 /// * `sequencer_commitment_opstack_2` - Optional second sequencer commitment
 /// * `env_input_opstack_for_l1_block_call_2` - Optional second Optimism environment input
@@ -518,6 +689,7 @@ pub fn get_validated_block_hash_linea(
     env_input_opstack_for_l1_block_call: Option<EthEvmInput>,
     env_input_eth_for_l1_inclusion: &Option<EthEvmInput>,
     block_header_to_validate: RlpHeader<Header>,
+    block_hash_to_validate: B256,
     validate_l1_inclusion: bool,
     sequencer_commitment_opstack_2: Option<SequencerCommitment>,
     env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
@@ -543,7 +715,7 @@ pub fn get_validated_block_hash_linea(
         );
     }
     validate_linea_env(chain_id, &block_header_to_validate);
-    block_header_to_validate.hash_slow()
+    block_hash_to_validate
 }
 
 /// Executes batch multicall for proof data queries.
@@ -555,6 +727,7 @@ pub fn get_validated_block_hash_linea(
 /// * `target_chain_ids` - Vector of target chain IDs
 /// * `env` - EVM environment for contract calls
 /// * `validate_l1_inclusion` - Whether L1 inclusion is being validated
+/// * `reorg_protection_depth` - Number of linking blocks validated above the queried state
 /// * `output` - Output vector for proof data results
 ///
 /// # Panics
@@ -569,6 +742,7 @@ pub fn batch_call_get_proof_data<H>(
     target_chain_ids: Vec<u64>,
     env: EvmEnv<StateDb, H, Commitment>,
     validate_l1_inclusion: bool,
+    reorg_protection_depth: u16,
     output: &mut Vec<Bytes>,
 ) where
     H: Clone + std::fmt::Debug,
@@ -581,14 +755,12 @@ pub fn batch_call_get_proof_data<H>(
         .zip(asset.iter())
         .zip(target_chain_ids.iter());
     for ((user, market), target_chain_id) in batch_params {
-        // Selector for getProofData(address,uint32)
-        let selector = [0x07, 0xd9, 0x23, 0xe9];
         let user_bytes: [u8; 32] = user.into_word().into();
         let chain_id_bytes: [u8; 32] = U256::from(*target_chain_id).to_be_bytes();
 
         // Create calldata by concatenating selector, encoded address, and chain ID
         let mut call_data = Vec::with_capacity(68); // 4 bytes selector + 32 bytes address + 32 bytes chain ID
-        call_data.extend_from_slice(&selector);
+        call_data.extend_from_slice(&GET_PROOF_DATA_SELECTOR);
         call_data.extend_from_slice(&user_bytes);
         call_data.extend_from_slice(&chain_id_bytes);
 
@@ -615,25 +787,70 @@ pub fn batch_call_get_proof_data<H>(
     // Zip the batch parameters with returns.results for parallel iteration
     batch_params.zip(returns.results.iter()).for_each(
         |(((user, market), target_chain_id), result)| {
-            let amounts = <(U256, U256)>::abi_decode(&result.returnData, true)
+            let amounts = Amounts::abi_decode(&result.returnData, true)
                 .expect("Failed to decode return data");
 
-            let input = vec![
-                SolidityDataType::Address(*user),
-                SolidityDataType::Address(*market),
-                SolidityDataType::Number(amounts.0), // amountIn
-                SolidityDataType::Number(amounts.1), // amountOut
-                SolidityDataType::NumberWithShift(U256::from(chain_id), TakeLastXBytes(32)),
-                SolidityDataType::NumberWithShift(U256::from(*target_chain_id), TakeLastXBytes(32)),
-                SolidityDataType::Bool(validate_l1_inclusion),
-            ];
-
-            let (bytes, _hash) = abi::encode_packed(&input);
-            output.push(bytes.into());
+            let entry = ProofDataJournalEntry {
+                user: *user,
+                market: *market,
+                amount_in: amounts.amountIn,
+                amount_out: amounts.amountOut,
+                chain_id,
+                target_chain_id: *target_chain_id,
+                l1_inclusion: validate_l1_inclusion,
+                reorg_protection_depth,
+            };
+            output.push(entry.encode_packed());
         },
     );
 }
 
+/// Executes batch multicall for arbitrary contract calls.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID for validation
+/// * `calls` - Contract calls to execute
+/// * `env` - EVM environment for contract calls
+/// * `validate_l1_inclusion` - Whether L1 inclusion is being validated
+/// * `reorg_protection_depth` - Number of linking blocks validated above the queried state
+/// * `output` - Output vector for the call results
+///
+/// # Panics
+/// Panics if any of the calls reverts
+pub fn batch_contract_call<H>(
+    chain_id: u64,
+    calls: Vec<ContractCall>,
+    env: EvmEnv<StateDb, H, Commitment>,
+    validate_l1_inclusion: bool,
+    reorg_protection_depth: u16,
+    output: &mut Vec<ContractCallJournalEntry>,
+) where
+    H: Clone + std::fmt::Debug,
+    H: EvmBlockHeader,
+{
+    let multicall_contract = Contract::new(MULTICALL, &env);
+
+    let multicall = IMulticall3::aggregate3Call {
+        calls: calls.iter().cloned().map(Call3::from).collect(),
+    };
+
+    let returns = multicall_contract.call_builder(&multicall).call();
+
+    calls
+        .into_iter()
+        .zip(returns.results)
+        .for_each(|(call, result)| {
+            output.push(ContractCallJournalEntry {
+                chainId: chain_id,
+                target: call.target,
+                callData: call.call_data,
+                returnData: result.returnData,
+                l1Inclusion: validate_l1_inclusion,
+                reorgProtectionDepth: reorg_protection_depth,
+            });
+        });
+}
+
 /// Validates Linea environment with L1 inclusion verification.
 ///
 /// This function verifies that a Linea block is properly included in the L1 chain
@@ -656,25 +873,56 @@ pub fn validate_linea_env_with_l1_inclusion(
     env_eth_input: &EthEvmInput,
     ethereum_hash: B256,
 ) {
-    let msg_service_address = match chain_id {
-        LINEA_CHAIN_ID => L1_MESSAGE_SERVICE_LINEA,
-        LINEA_SEPOLIA_CHAIN_ID => L1_MESSAGE_SERVICE_LINEA_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
-
     let env_eth = env_eth_input.clone().into_env();
 
     let eth_hash = env_eth.header().seal();
 
     assert_eq!(ethereum_hash, eth_hash, "Ethereum hash mismatch linea");
 
+    validate_linea_block_posted_to_l1(chain_id, env_block_number, &env_eth);
+}
+
+/// Validates that a Linea block was posted to L1 as of a validated Ethereum environment.
+///
+/// The Ethereum environment must already be bound to a validated Ethereum block, either via
+/// OpStack's L1Block, see `validate_linea_env_with_l1_inclusion`, or via the light client.
+///
+/// # Arguments
+/// * `chain_id` - The Linea chain ID
+/// * `env_block_number` - The block number to validate
+/// * `env_eth` - The validated Ethereum environment
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is invalid
+/// * Block number is higher than the last one posted to L1
+pub fn validate_linea_block_posted_to_l1<H>(
+    chain_id: u64,
+    env_block_number: u64,
+    env_eth: &EvmEnv<StateDb, H, Commitment>,
+) where
+    H: Clone + std::fmt::Debug,
+    H: EvmBlockHeader,
+{
+    let msg_service_address = linea_l1_message_service(chain_id);
+
     let current_l2_block_number_call = IL1MessageService::currentL2BlockNumberCall {};
 
-    let contract = Contract::new(msg_service_address, &env_eth);
+    let contract = Contract::new(msg_service_address, env_eth);
     let returns = contract.call_builder(&current_l2_block_number_call).call();
 
-    let l2_block_number = returns._0;
+    validate_linea_block_number_posted(returns._0, env_block_number);
+}
 
+/// Validates a Linea block number against the last Linea block posted to L1.
+///
+/// # Arguments
+/// * `l2_block_number` - The last Linea block number posted to L1
+/// * `env_block_number` - The block number to validate
+///
+/// # Panics
+/// Panics if the block number is higher than the last one posted to L1
+pub fn validate_linea_block_number_posted(l2_block_number: U256, env_block_number: u64) {
     assert!(
         l2_block_number >= U256::from(env_block_number),
         "Block number must be lower than or equal to the last one posted to L1"
@@ -694,7 +942,54 @@ pub fn validate_linea_env_with_l1_inclusion(
 /// * Signature recovery fails
 /// * Extra data format is invalid
 pub fn validate_linea_env(chain_id: u64, block_header_to_validate: &RlpHeader<Header>) {
-    let extra_data = block_header_to_validate.inner().extra_data.clone();
+    let expected_sequencer = match chain_id {
+        LINEA_CHAIN_ID => LINEA_SEQUENCER,
+        LINEA_SEPOLIA_CHAIN_ID => LINEA_SEPOLIA_SEQUENCER,
+        _ => panic!("invalid chain id"),
+    };
+
+    validate_linea_env_with_sequencers(&[expected_sequencer], block_header_to_validate);
+}
+
+/// Validates a Linea block header against a given set of sequencers.
+///
+/// # Arguments
+/// * `sequencers` - Addresses accepted as the block signer
+/// * `block_header_to_validate` - The Linea block header to validate
+///
+/// # Panics
+/// Panics if:
+/// * Block is not signed by one of `sequencers`
+/// * Signature recovery fails
+/// * Extra data format is invalid
+pub fn validate_linea_env_with_sequencers(
+    sequencers: &[Address],
+    block_header_to_validate: &RlpHeader<Header>,
+) {
+    let sequencer = recover_linea_signer(block_header_to_validate);
+
+    if !sequencers.contains(&sequencer) {
+        panic!("Block not signed by linea sequencer");
+    }
+}
+
+/// Recovers the address that signed a Linea block header.
+///
+/// The signature is the last 65 bytes of `extra_data` and covers the header hash with the
+/// signature stripped from `extra_data`.
+///
+/// # Arguments
+/// * `block_header` - The signed Linea block header
+///
+/// # Returns
+/// * `Address` - The recovered signer
+///
+/// # Panics
+/// Panics if:
+/// * Extra data format is invalid
+/// * Signature recovery fails
+pub fn recover_linea_signer(block_header: &RlpHeader<Header>) -> Address {
+    let extra_data = block_header.inner().extra_data.clone();
 
     let length = extra_data.len();
     let prefix = extra_data.slice(0..length - 65);
@@ -706,7 +1001,7 @@ pub fn validate_linea_env(chain_id: u64, block_header_to_validate: &RlpHeader<He
             .expect("Failed to convert signature bytes to fixed array"),
     );
 
-    let mut header = block_header_to_validate.inner().clone();
+    let mut header = block_header.inner().clone();
     header.extra_data = prefix;
 
     let sighash: [u8; 32] = header
@@ -716,18 +1011,7 @@ pub fn validate_linea_env(chain_id: u64, block_header_to_validate: &RlpHeader<He
         .expect("Failed to convert header hash to fixed array");
     let sighash = B256::new(sighash);
 
-    let sequencer =
-        recover_signer(sig, sighash).expect("Failed to recover sequencer address from signature");
-
-    let expected_sequencer = match chain_id {
-        LINEA_CHAIN_ID => LINEA_SEQUENCER,
-        LINEA_SEPOLIA_CHAIN_ID => LINEA_SEPOLIA_SEQUENCER,
-        _ => panic!("invalid chain id"),
-    };
-
-    if sequencer != expected_sequencer {
-        panic!("Block not signed by linea sequencer");
-    }
+    recover_signer(sig, sighash).expect("Failed to recover sequencer address from signature")
 }
 
 /// Validates an OpStack (Optimism/Base) environment through sequencer commitments.
@@ -740,26 +1024,46 @@ pub fn validate_linea_env(chain_id: u64, block_header_to_validate: &RlpHeader<He
 /// # Panics
 /// Panics if:
 /// * Chain ID is not an OpStack chain
-/// * Commitment verification fails
+/// * Commitment is not signed by one of the chain's accepted sequencers (`opstack_sequencers`)
 /// * Block hash doesn't match commitment
-/// * Sequencer signature is invalid
 /// * Execution payload conversion fails
 pub fn validate_opstack_env(chain_id: u64, commitment: &SequencerCommitment, env_block_hash: B256) {
-    match chain_id {
-        OPTIMISM_CHAIN_ID => commitment
-            .verify(OPTIMISM_SEQUENCER, OPTIMISM_CHAIN_ID)
-            .expect("Failed to verify Optimism sequencer commitment"),
-        BASE_CHAIN_ID => commitment
-            .verify(BASE_SEQUENCER, BASE_CHAIN_ID)
-            .expect("Failed to verify Base sequencer commitment"),
-        OPTIMISM_SEPOLIA_CHAIN_ID => commitment
-            .verify(OPTIMISM_SEPOLIA_SEQUENCER, OPTIMISM_SEPOLIA_CHAIN_ID)
-            .expect("Failed to verify Optimism Sepolia sequencer commitment"),
-        BASE_SEPOLIA_CHAIN_ID => commitment
-            .verify(BASE_SEPOLIA_SEQUENCER, BASE_SEPOLIA_CHAIN_ID)
-            .expect("Failed to verify Base Sepolia sequencer commitment"),
-        _ => panic!("invalid chain id"),
-    }
+    validate_opstack_env_with_sequencers(
+        opstack_sequencers(chain_id),
+        chain_id,
+        commitment,
+        env_block_hash,
+    );
+}
+
+/// Validates an OpStack environment through a sequencer commitment signed by one of
+/// `sequencers`.
+///
+/// # Arguments
+/// * `sequencers` - Addresses accepted as the commitment signer
+/// * `chain_id` - The chain ID the commitment is signed for
+/// * `commitment` - The sequencer commitment to verify
+/// * `env_block_hash` - The block hash to validate against
+///
+/// # Panics
+/// Panics if:
+/// * Commitment is not signed by one of `sequencers`
+/// * Block hash doesn't match commitment
+/// * Execution payload conversion fails
+pub fn validate_opstack_env_with_sequencers(
+    sequencers: &[Address],
+    chain_id: u64,
+    commitment: &SequencerCommitment,
+    env_block_hash: B256,
+) {
+    commitment
+        .verify_any(sequencers, chain_id)
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to verify sequencer commitment for chain {}",
+                chain_id
+            )
+        });
     let payload = ExecutionPayload::try_from(commitment)
         .expect("Failed to convert sequencer commitment to execution payload");
     assert_eq!(payload.block_hash, env_block_hash, "block hash mismatch");
@@ -769,13 +1073,15 @@ pub fn validate_opstack_env(chain_id: u64, commitment: &SequencerCommitment, env
 ///
 /// Uses Optimism's L1Block contract to fetch and verify the L1 block hash.
 /// This provides a secure way to verify L1 block hashes through L2 commitments.
+/// When a second environment is provided, the L1 block hash is additionally attested
+/// through Base and both attestations must agree.
 ///
 /// # Arguments
 /// * `sequencer_commitment_opstack_1` - The Optimism sequencer commitment
 /// * `env_input_opstack_for_l1_block_call_1` - The Optimism EVM input containing environment data
 /// * `chain_id` - The Ethereum chain ID (mainnet or Sepolia)
-/// * `_sequencer_commitment_opstack_2` - (Unused) Optional second sequencer commitment
-/// * `_env_input_opstack_for_l1_block_call_2` - (Unused) Optional second Optimism EVM input
+/// * `sequencer_commitment_opstack_2` - Optional Base sequencer commitment
+/// * `env_input_opstack_for_l1_block_call_2` - Optional Base EVM input containing environment data
 ///
 /// # Returns
 /// * `B256` - The validated Ethereum block hash
@@ -785,42 +1091,131 @@ pub fn validate_opstack_env(chain_id: u64, commitment: &SequencerCommitment, env
 /// * OpStack environment validation fails
 /// * L1Block contract call fails
 /// * Chain ID is not an Ethereum chain
+/// * The second environment is provided without its sequencer commitment
+/// * The Optimism and Base L1 block hashes don't match
 pub fn get_validated_ethereum_block_hash_via_opstack(
     sequencer_commitment_opstack_1: Option<&SequencerCommitment>,
     env_input_opstack_for_l1_block_call_1: Option<EthEvmInput>,
     chain_id: u64,
-    _sequencer_commitment_opstack_2: Option<&SequencerCommitment>,
-    _env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
+    sequencer_commitment_opstack_2: Option<&SequencerCommitment>,
+    env_input_opstack_for_l1_block_call_2: Option<EthEvmInput>,
 ) -> B256 {
     let env_op = env_input_opstack_for_l1_block_call_1
         .expect("env_input_opstack_for_l1_block_call_1 is None")
         .into_env();
 
-    let (verify_via_chain_1, _verify_via_chain_2) = if chain_id == ETHEREUM_CHAIN_ID {
+    let (verify_via_chain_1, verify_via_chain_2) = if chain_id == ETHEREUM_CHAIN_ID {
         (OPTIMISM_CHAIN_ID, BASE_CHAIN_ID)
     } else {
         (OPTIMISM_SEPOLIA_CHAIN_ID, BASE_SEPOLIA_CHAIN_ID)
     };
-    validate_opstack_env(
-        verify_via_chain_1,
-        sequencer_commitment_opstack_1.unwrap(),
-        env_op.commitment().digest,
-    );
 
-    let l1_block = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op);
+    let l1_block = Contract::new(l1_block_address(verify_via_chain_1), &env_op);
     let call = IL1Block::hashCall {};
-    let l1_hash_1 = l1_block.call_builder(&call).call()._0;
+    let attestation_1 = L1BlockHashAttestation {
+        chain_id: verify_via_chain_1,
+        sequencers: opstack_sequencers(verify_via_chain_1),
+        commitment: sequencer_commitment_opstack_1.unwrap(),
+        block_hash: env_op.commitment().digest,
+        l1_block_hash: l1_block.call_builder(&call).call()._0,
+    };
 
-    // let env_op_2 = env_input_opstack_for_l1_block_call_2.expect("env_input_opstack_for_l1_block_call_2 is None").into_env();
-    // validate_opstack_env(verify_via_chain_2, sequencer_commitment_opstack_2.unwrap(), env_op_2.commitment().digest);
+    let attestation_2 = env_input_opstack_for_l1_block_call_2.map(|env_input| {
+        let env_op_2 = env_input.into_env();
+        let l1_block = Contract::new(l1_block_address(verify_via_chain_2), &env_op_2);
+        let call = IL1Block::hashCall {};
+        L1BlockHashAttestation {
+            chain_id: verify_via_chain_2,
+            sequencers: opstack_sequencers(verify_via_chain_2),
+            commitment: sequencer_commitment_opstack_2
+                .expect("sequencer_commitment_opstack_2 is None"),
+            block_hash: env_op_2.commitment().digest,
+            l1_block_hash: l1_block.call_builder(&call).call()._0,
+        }
+    });
+
+    validate_l1_block_hash_attestations(attestation_1, attestation_2)
+}
 
-    // let l1_block = Contract::new(L1_BLOCK_ADDRESS_OPSTACK, &env_op_2);
-    // let call = IL1Block::hashCall {};
-    // let l1_hash_2 = l1_block.call_builder(&call).call()._0;
+/// An Ethereum block hash read from an OpStack chain's `L1Block` predeploy.
+#[derive(Debug, Clone, Copy)]
+pub struct L1BlockHashAttestation<'a> {
+    /// The OpStack chain the hash was read on
+    pub chain_id: u64,
+    /// Sequencers accepted for `chain_id`, see `opstack_sequencers`
+    pub sequencers: &'a [Address],
+    /// Sequencer commitment to the OpStack block the hash was read at
+    pub commitment: &'a SequencerCommitment,
+    /// Hash of the OpStack block the hash was read at
+    pub block_hash: B256,
+    /// The Ethereum block hash returned by `L1Block`
+    pub l1_block_hash: B256,
+}
 
-    // assert_eq!(l1_hash_1, l1_hash_2, "L1 hash 1 and 2 mismatch");
+/// Validates one or two OpStack attestations of an Ethereum block hash.
+///
+/// # Arguments
+/// * `attestation_1` - The Optimism attestation
+/// * `attestation_2` - Optional Base attestation for dual L1 attestation
+///
+/// # Returns
+/// * `B256` - The validated Ethereum block hash
+///
+/// # Panics
+/// Panics if:
+/// * An OpStack block is not validated by its sequencer commitment, see
+///   `validate_opstack_env_with_sequencers`
+/// * The Optimism and Base L1 block hashes don't match
+pub fn validate_l1_block_hash_attestations(
+    attestation_1: L1BlockHashAttestation,
+    attestation_2: Option<L1BlockHashAttestation>,
+) -> B256 {
+    validate_opstack_env_with_sequencers(
+        attestation_1.sequencers,
+        attestation_1.chain_id,
+        attestation_1.commitment,
+        attestation_1.block_hash,
+    );
+
+    if let Some(attestation_2) = attestation_2 {
+        validate_opstack_env_with_sequencers(
+            attestation_2.sequencers,
+            attestation_2.chain_id,
+            attestation_2.commitment,
+            attestation_2.block_hash,
+        );
+        assert_eq!(
+            attestation_1.l1_block_hash, attestation_2.l1_block_hash,
+            "L1 hash 1 and 2 mismatch"
+        );
+    }
+
+    attestation_1.l1_block_hash
+}
 
-    l1_hash_1
+/// Block header that can be hash-linked to its parent.
+pub trait ChainHeader {
+    /// Returns the hash of the parent block.
+    fn parent_hash(&self) -> B256;
+    /// Computes the hash of the block.
+    fn hash_slow(&self) -> B256;
+    /// Returns the block number.
+    fn number(&self) -> u64;
+}
+
+/// Execution layer headers; the light client's `ConsensusHeader` is the same alloy header.
+impl ChainHeader for RlpHeader<Header> {
+    fn parent_hash(&self) -> B256 {
+        self.inner().parent_hash
+    }
+
+    fn hash_slow(&self) -> B256 {
+        self.inner().hash_slow()
+    }
+
+    fn number(&self) -> u64 {
+        self.inner().number
+    }
 }
 
 /// Validates block chain length and hash linking for reorg protection.
@@ -828,6 +1223,11 @@ pub fn get_validated_ethereum_block_hash_via_opstack(
 /// Ensures sufficient block confirmations and proper hash linking between blocks
 /// to prevent reorganization attacks.
 ///
+/// On chains with a reorg protection depth of 0, e.g. the Sepolia testnets, the historical
+/// block may be the validated block itself. There is nothing to link then, so the historical
+/// hash must be exactly the current hash, which the caller validated against the sequencer
+/// commitment, dispute game or L1.
+///
 /// # Arguments
 /// * `chain_id` - The chain ID to determine reorg protection depth
 /// * `historical_hash` - The hash of the historical block
@@ -837,41 +1237,482 @@ pub fn get_validated_ethereum_block_hash_via_opstack(
 /// # Panics
 /// Panics if:
 /// * Chain length is less than required reorg protection depth
+/// * Without linking blocks, the historical hash doesn't match current hash
 /// * Blocks are not properly hash-linked
 /// * Final hash doesn't match current hash
 /// * Chain ID is invalid or unsupported
-pub fn validate_chain_length(
+pub fn validate_chain_length<H: ChainHeader>(
     chain_id: u64,
     historical_hash: B256,
-    linking_blocks: &Vec<RlpHeader<Header>>,
+    linking_blocks: &[H],
     current_hash: B256,
 ) {
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        SCROLL_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        SCROLL_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
+    validate_chain_length_with_hashes(
+        chain_id,
+        historical_hash,
+        linking_blocks,
+        &hash_linking_blocks(linking_blocks),
+        current_hash,
+    );
+}
+
+/// Validates block chain length and hash linking with precomputed linking block hashes.
+///
+/// Same as `validate_chain_length`, for callers that also need the linking block hashes
+/// elsewhere. Hashing a header is a keccak over its RLP encoding, so in the zkVM every
+/// linking block should be hashed only once.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to determine reorg protection depth
+/// * `historical_hash` - The hash of the historical block
+/// * `linking_blocks` - Vector of blocks linking historical to current
+/// * `linking_block_hashes` - Hashes of `linking_blocks`, see `hash_linking_blocks`
+/// * `current_hash` - The expected current block hash
+///
+/// # Panics
+/// Panics if:
+/// * There isn't exactly one hash per linking block
+/// * Any of the checks of `validate_chain_length` fails
+pub fn validate_chain_length_with_hashes<H: ChainHeader>(
+    chain_id: u64,
+    historical_hash: B256,
+    linking_blocks: &[H],
+    linking_block_hashes: &[B256],
+    current_hash: B256,
+) {
+    assert_eq!(
+        linking_blocks.len(),
+        linking_block_hashes.len(),
+        "linking block hashes don't match linking blocks"
+    );
+    let reorg_protection_depth = reorg_protection_depth(chain_id).expect("invalid chain id");
     let chain_length = linking_blocks.len() as u64;
     assert!(
         chain_length >= reorg_protection_depth,
         "chain length is less than reorg protection"
     );
+    if linking_blocks.is_empty() {
+        // Zero depth: the queried env header is the validated block itself
+        assert_eq!(
+            historical_hash, current_hash,
+            "env header doesnt correspond to verified hash"
+        );
+        return;
+    }
     let mut previous_hash = historical_hash;
-    for header in linking_blocks.iter() {
-        let parent_hash = header.parent_hash;
+    for (header, hash) in linking_blocks.iter().zip(linking_block_hashes) {
+        let parent_hash = header.parent_hash();
         assert_eq!(parent_hash, previous_hash, "blocks not hashlinked");
-        previous_hash = header.hash_slow();
+        previous_hash = *hash;
     }
     assert_eq!(
         previous_hash, current_hash,
         "last hash doesnt correspond to verified hash"
     );
 }
+
+/// Hashes every linking block once.
+///
+/// # Arguments
+/// * `linking_blocks` - Vector of blocks linking historical to current
+///
+/// # Returns
+/// * `Vec<B256>` - The block hashes, in the order of `linking_blocks`
+pub fn hash_linking_blocks<H: ChainHeader>(linking_blocks: &[H]) -> Vec<B256> {
+    linking_blocks.iter().map(ChainHeader::hash_slow).collect()
+}
+
+/// Validates that linking blocks directly follow a historical block by block number.
+///
+/// Hash linking already binds the blocks to the historical block; this additionally rejects
+/// headers of another chain that only link among themselves.
+///
+/// # Arguments
+/// * `historical_number` - The number of the historical block
+/// * `linking_blocks` - Vector of blocks linking historical to current
+///
+/// # Panics
+/// Panics if a block's number doesn't directly follow its predecessor's
+pub fn validate_chain_numbers<H: ChainHeader>(historical_number: u64, linking_blocks: &[H]) {
+    let mut previous_number = historical_number;
+    for header in linking_blocks.iter() {
+        assert_eq!(
+            header.number(),
+            previous_number + 1,
+            "blocks not contiguous"
+        );
+        previous_number = header.number();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        linked_headers, signed_linea_header, signed_payload_commitment,
+        signed_sequencer_commitment, signer_address, test_signing_key, HeaderBuilder,
+    };
+
+    #[test]
+    fn test_validate_chain_length_of_reorg_depth() {
+        let historical_hash = B256::repeat_byte(0x01);
+        let blocks = linked_headers(historical_hash, REORG_PROTECTION_DEPTH_OPTIMISM);
+        let current_hash = blocks.last().unwrap().hash_slow();
+
+        validate_chain_length(OPTIMISM_CHAIN_ID, historical_hash, &blocks, current_hash);
+    }
+
+    #[test]
+    fn test_validate_chain_length_with_precomputed_hashes() {
+        let historical_hash = B256::repeat_byte(0x01);
+        let blocks = linked_headers(historical_hash, REORG_PROTECTION_DEPTH_OPTIMISM);
+        let hashes = hash_linking_blocks(&blocks);
+        assert_eq!(hashes[0], blocks[0].hash_slow());
+
+        validate_chain_length_with_hashes(
+            OPTIMISM_CHAIN_ID,
+            historical_hash,
+            &blocks,
+            &hashes,
+            *hashes.last().unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "linking block hashes don't match linking blocks")]
+    fn test_validate_chain_length_with_missing_hash_panics() {
+        let historical_hash = B256::repeat_byte(0x01);
+        let blocks = linked_headers(historical_hash, REORG_PROTECTION_DEPTH_OPTIMISM);
+        let hashes = hash_linking_blocks(&blocks[1..]);
+
+        validate_chain_length_with_hashes(
+            OPTIMISM_CHAIN_ID,
+            historical_hash,
+            &blocks,
+            &hashes,
+            *hashes.last().unwrap(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "chain length is less than reorg protection")]
+    fn test_validate_chain_length_one_short_panics() {
+        let historical_hash = B256::repeat_byte(0x01);
+        let blocks = linked_headers(historical_hash, REORG_PROTECTION_DEPTH_LINEA - 1);
+        let current_hash = blocks
+            .last()
+            .map_or(historical_hash, |header| header.hash_slow());
+
+        validate_chain_length(LINEA_CHAIN_ID, historical_hash, &blocks, current_hash);
+    }
+
+    #[test]
+    #[should_panic(expected = "blocks not hashlinked")]
+    fn test_validate_chain_length_broken_link_panics() {
+        let historical_hash = B256::repeat_byte(0x01);
+        let mut blocks = linked_headers(historical_hash, REORG_PROTECTION_DEPTH_OPTIMISM + 2);
+        let current_hash = blocks.last().unwrap().hash_slow();
+        blocks[1] = HeaderBuilder::new()
+            .parent_hash(B256::repeat_byte(0x02))
+            .number(2)
+            .build();
+
+        validate_chain_length(OPTIMISM_CHAIN_ID, historical_hash, &blocks, current_hash);
+    }
+
+    #[test]
+    #[should_panic(expected = "last hash doesnt correspond to verified hash")]
+    fn test_validate_chain_length_wrong_current_hash_panics() {
+        let historical_hash = B256::repeat_byte(0x01);
+        let blocks = linked_headers(historical_hash, REORG_PROTECTION_DEPTH_OPTIMISM);
+
+        validate_chain_length(
+            OPTIMISM_CHAIN_ID,
+            historical_hash,
+            &blocks,
+            B256::repeat_byte(0x03),
+        );
+    }
+
+    #[test]
+    fn test_validate_chain_length_zero_depth_env_header() {
+        let env_header = HeaderBuilder::new().number(1).build();
+        let env_hash = env_header.hash_slow();
+
+        validate_chain_length::<RlpHeader<Header>>(
+            OPTIMISM_SEPOLIA_CHAIN_ID,
+            env_hash,
+            &[],
+            env_hash,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "env header doesnt correspond to verified hash")]
+    fn test_validate_chain_length_zero_depth_other_header_panics() {
+        let env_header = HeaderBuilder::new().number(1).build();
+        let validated_header = HeaderBuilder::new().number(2).build();
+
+        validate_chain_length::<RlpHeader<Header>>(
+            OPTIMISM_SEPOLIA_CHAIN_ID,
+            env_header.hash_slow(),
+            &[],
+            validated_header.hash_slow(),
+        );
+    }
+
+    #[test]
+    fn test_validate_chain_length_zero_depth_with_linking_blocks() {
+        // Safe block queries link to the validated head even when no depth is required
+        let historical_hash = B256::repeat_byte(0x01);
+        let blocks = linked_headers(historical_hash, 2);
+        let current_hash = blocks.last().unwrap().hash_slow();
+
+        validate_chain_length(
+            LINEA_SEPOLIA_CHAIN_ID,
+            historical_hash,
+            &blocks,
+            current_hash,
+        );
+    }
+
+    #[test]
+    fn test_validate_chain_numbers_contiguous() {
+        let blocks = linked_headers(B256::repeat_byte(0x01), 3);
+
+        validate_chain_numbers(0, &blocks);
+    }
+
+    #[test]
+    #[should_panic(expected = "blocks not contiguous")]
+    fn test_validate_chain_numbers_foreign_chain_panics() {
+        // Internally consistent headers of another chain, numbered 1.., after block 1000
+        let blocks = linked_headers(B256::repeat_byte(0x01), REORG_PROTECTION_DEPTH_OPTIMISM);
+
+        validate_chain_numbers(1000, &blocks);
+    }
+
+    #[test]
+    fn test_validate_linea_block_number_posted() {
+        validate_linea_block_number_posted(U256::from(100), 99);
+        validate_linea_block_number_posted(U256::from(100), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Block number must be lower than or equal to the last one posted")]
+    fn test_validate_linea_block_number_not_posted_panics() {
+        validate_linea_block_number_posted(U256::from(100), 101);
+    }
+
+    #[test]
+    fn test_recover_linea_signer() {
+        let signing_key = test_signing_key(0x11);
+        let header = signed_linea_header(
+            HeaderBuilder::new()
+                .number(7)
+                .extra_data(Bytes::from(vec![0u8; 32]))
+                .header(),
+            &signing_key,
+        );
+
+        assert_eq!(recover_linea_signer(&header), signer_address(&signing_key));
+        assert_ne!(
+            recover_linea_signer(&header),
+            signer_address(&test_signing_key(0x22))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Block not signed by linea sequencer")]
+    fn test_validate_linea_env_other_signer_panics() {
+        let header = signed_linea_header(HeaderBuilder::new().header(), &test_signing_key(0x11));
+
+        validate_linea_env(LINEA_CHAIN_ID, &header);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to verify sequencer commitment")]
+    fn test_validate_opstack_env_other_signer_panics() {
+        let commitment = signed_sequencer_commitment(
+            Bytes::from(vec![0xab; 64]),
+            OPTIMISM_CHAIN_ID,
+            &test_signing_key(0x11),
+        );
+
+        validate_opstack_env(OPTIMISM_CHAIN_ID, &commitment, B256::ZERO);
+    }
+
+    #[test]
+    fn test_validate_linea_env_with_sequencers_accepts_signer() {
+        let signing_key = test_signing_key(0x11);
+        let header = signed_linea_header(
+            HeaderBuilder::new()
+                .number(7)
+                .extra_data(Bytes::from(vec![0u8; 32]))
+                .header(),
+            &signing_key,
+        );
+
+        validate_linea_env_with_sequencers(&[signer_address(&signing_key)], &header);
+    }
+
+    #[test]
+    #[should_panic(expected = "Block not signed by linea sequencer")]
+    fn test_validate_linea_env_with_sequencers_other_signer_panics() {
+        let header = signed_linea_header(HeaderBuilder::new().header(), &test_signing_key(0x11));
+
+        validate_linea_env_with_sequencers(&[signer_address(&test_signing_key(0x22))], &header);
+    }
+
+    #[test]
+    fn test_validate_opstack_env_with_sequencers_accepts_signer() {
+        let signing_key = test_signing_key(0x11);
+        let block_hash = B256::repeat_byte(0x01);
+        let commitment = signed_payload_commitment(block_hash, OPTIMISM_CHAIN_ID, &signing_key);
+
+        validate_opstack_env_with_sequencers(
+            &[signer_address(&signing_key)],
+            OPTIMISM_CHAIN_ID,
+            &commitment,
+            block_hash,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "block hash mismatch")]
+    fn test_validate_opstack_env_with_sequencers_other_block_panics() {
+        let signing_key = test_signing_key(0x11);
+        let commitment =
+            signed_payload_commitment(B256::repeat_byte(0x01), OPTIMISM_CHAIN_ID, &signing_key);
+
+        validate_opstack_env_with_sequencers(
+            &[signer_address(&signing_key)],
+            OPTIMISM_CHAIN_ID,
+            &commitment,
+            B256::repeat_byte(0x02),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to verify sequencer commitment")]
+    fn test_validate_opstack_env_with_sequencers_other_chain_panics() {
+        // A commitment signed for Base doesn't verify as an Optimism commitment
+        let signing_key = test_signing_key(0x11);
+        let block_hash = B256::repeat_byte(0x01);
+        let commitment = signed_payload_commitment(block_hash, BASE_CHAIN_ID, &signing_key);
+
+        validate_opstack_env_with_sequencers(
+            &[signer_address(&signing_key)],
+            OPTIMISM_CHAIN_ID,
+            &commitment,
+            block_hash,
+        );
+    }
+
+    /// Builds an attestation of `l1_block_hash` read at the block `commitment` commits to.
+    fn l1_block_hash_attestation(
+        chain_id: u64,
+        l1_block_hash: B256,
+        sequencers: &[Address],
+        commitment: &SequencerCommitment,
+    ) -> L1BlockHashAttestation<'_> {
+        L1BlockHashAttestation {
+            chain_id,
+            sequencers,
+            commitment,
+            block_hash: ExecutionPayload::try_from(commitment).unwrap().block_hash,
+            l1_block_hash,
+        }
+    }
+
+    #[test]
+    fn test_validate_l1_block_hash_attestations_via_optimism_and_base() {
+        let optimism_key = test_signing_key(0x11);
+        let base_key = test_signing_key(0x22);
+        let optimism_sequencers = [signer_address(&optimism_key)];
+        let base_sequencers = [signer_address(&base_key)];
+        let optimism_commitment =
+            signed_payload_commitment(B256::repeat_byte(0x01), OPTIMISM_CHAIN_ID, &optimism_key);
+        let base_commitment =
+            signed_payload_commitment(B256::repeat_byte(0x02), BASE_CHAIN_ID, &base_key);
+        let l1_block_hash = B256::repeat_byte(0xee);
+
+        let attestation_1 = l1_block_hash_attestation(
+            OPTIMISM_CHAIN_ID,
+            l1_block_hash,
+            &optimism_sequencers,
+            &optimism_commitment,
+        );
+        let attestation_2 = l1_block_hash_attestation(
+            BASE_CHAIN_ID,
+            l1_block_hash,
+            &base_sequencers,
+            &base_commitment,
+        );
+
+        assert_eq!(
+            validate_l1_block_hash_attestations(attestation_1, None),
+            l1_block_hash
+        );
+        assert_eq!(
+            validate_l1_block_hash_attestations(attestation_1, Some(attestation_2)),
+            l1_block_hash
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "L1 hash 1 and 2 mismatch")]
+    fn test_validate_l1_block_hash_attestations_mismatch_panics() {
+        let signing_key = test_signing_key(0x11);
+        let sequencers = [signer_address(&signing_key)];
+        let optimism_commitment =
+            signed_payload_commitment(B256::repeat_byte(0x01), OPTIMISM_CHAIN_ID, &signing_key);
+        let base_commitment =
+            signed_payload_commitment(B256::repeat_byte(0x02), BASE_CHAIN_ID, &signing_key);
+
+        validate_l1_block_hash_attestations(
+            l1_block_hash_attestation(
+                OPTIMISM_CHAIN_ID,
+                B256::repeat_byte(0xee),
+                &sequencers,
+                &optimism_commitment,
+            ),
+            Some(l1_block_hash_attestation(
+                BASE_CHAIN_ID,
+                B256::repeat_byte(0xef),
+                &sequencers,
+                &base_commitment,
+            )),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to verify sequencer commitment for chain 8453")]
+    fn test_validate_l1_block_hash_attestations_foreign_base_signer_panics() {
+        let signing_key = test_signing_key(0x11);
+        let sequencers = [signer_address(&signing_key)];
+        let optimism_commitment =
+            signed_payload_commitment(B256::repeat_byte(0x01), OPTIMISM_CHAIN_ID, &signing_key);
+        let base_commitment = signed_payload_commitment(
+            B256::repeat_byte(0x02),
+            BASE_CHAIN_ID,
+            &test_signing_key(0x22),
+        );
+        let l1_block_hash = B256::repeat_byte(0xee);
+
+        validate_l1_block_hash_attestations(
+            l1_block_hash_attestation(
+                OPTIMISM_CHAIN_ID,
+                l1_block_hash,
+                &sequencers,
+                &optimism_commitment,
+            ),
+            Some(l1_block_hash_attestation(
+                BASE_CHAIN_ID,
+                l1_block_hash,
+                &sequencers,
+                &base_commitment,
+            )),
+        );
+    }
+}
@@ -35,7 +35,7 @@ use alloy_sol_types::sol;
 use eyre::Result;
 use tree_hash::TreeHash;
 
-use alloy_primitives::Address;
+use alloy_primitives::{Address, Bytes};
 use risc0_steel::ethereum::EthEvmInput;
 use risc0_zkvm::guest::env;
 
@@ -43,6 +43,10 @@ use consensus_core::types::{SyncAggregate, SyncCommittee};
 
 use crate::constants::*;
 use crate::types::*;
+use crate::validators::{
+    hash_linking_blocks, validate_chain_length_with_hashes, validate_chain_numbers,
+    validate_linea_block_posted_to_l1, validate_linea_env,
+};
 use alloy_consensus::Header as ConsensusHeader;
 use alloy_sol_types::SolValue;
 use risc0_steel::{serde::RlpHeader, Contract};
@@ -68,8 +72,18 @@ impl L1ChainBuilder {
     /// - Deneb fork configuration
     /// - Mainnet genesis parameters
     pub fn new() -> Self {
-        let store = LightClientStore::default();
+        Self::from_store(LightClientStore::default(), None)
+    }
 
+    /// Creates a mainnet L1ChainBuilder resuming from a previously synced store.
+    ///
+    /// Lets a host persist the store between proofs and apply only new updates with
+    /// `advance` instead of bootstrapping again.
+    ///
+    /// # Arguments
+    /// * `store` - Light client store of an earlier sync
+    /// * `last_checkpoint` - Last checkpoint reached by that sync, if any
+    pub fn from_store(store: LightClientStore, last_checkpoint: Option<B256>) -> Self {
         let mut forks = Forks::default();
         forks.deneb.epoch = 269568;
         forks.deneb.fork_version = old_fixed_bytes!("04000000");
@@ -79,7 +93,7 @@ impl L1ChainBuilder {
 
         L1ChainBuilder {
             store,
-            last_checkpoint: None,
+            last_checkpoint,
             genesis_root,
             forks,
             genesis_time,
@@ -104,6 +118,22 @@ impl L1ChainBuilder {
         optimistic_update: OptimisticUpdate,
     ) -> Result<B256> {
         self.bootstrap(bootstrap, checkpoint)?;
+        self.advance(updates, optimistic_update)
+    }
+
+    /// Applies updates to an already bootstrapped store.
+    ///
+    /// # Arguments
+    /// * `updates` - Vector of light client updates
+    /// * `optimistic_update` - Latest optimistic update
+    ///
+    /// # Returns
+    /// * Latest beacon chain root after applying all updates
+    pub fn advance(
+        &mut self,
+        updates: Vec<Update>,
+        optimistic_update: OptimisticUpdate,
+    ) -> Result<B256> {
         self.advance_updates(updates)?;
         self.advance_optimistic_update(optimistic_update)?;
         let latest_beacon_root = self.store.optimistic_header.beacon.tree_hash_root();
@@ -116,7 +146,7 @@ impl L1ChainBuilder {
     /// * `bootstrap` - Bootstrap data containing initial header and sync committee
     /// * `checkpoint` - Trust checkpoint to verify against
     pub fn bootstrap(&mut self, bootstrap: Bootstrap, checkpoint: OldB256) -> Result<()> {
-        verify_bootstrap(&bootstrap, checkpoint, &self.forks).unwrap();
+        verify_bootstrap(&bootstrap, checkpoint, &self.forks)?;
         apply_bootstrap(&mut self.store, &bootstrap);
         Ok(())
     }
@@ -180,10 +210,13 @@ impl L1ChainBuilder {
     ///
     /// # Arguments
     /// * `update` - Verified update to apply
+    ///
+    /// # Panics
+    /// Panics if a new checkpoint is not the root of the store's finalized header
     pub fn apply_update(&mut self, update: &Update) {
         let new_checkpoint = apply_update(&mut self.store, update);
         if new_checkpoint.is_some() {
-            self.last_checkpoint = Some(B256::new(new_checkpoint.unwrap().0));
+            self.set_checkpoint(B256::new(new_checkpoint.unwrap().0));
         }
     }
 
@@ -194,9 +227,23 @@ impl L1ChainBuilder {
     fn apply_optimistic_update(&mut self, update: &OptimisticUpdate) {
         let new_checkpoint = apply_optimistic_update(&mut self.store, update);
         if new_checkpoint.is_some() {
-            self.last_checkpoint = Some(B256::new(new_checkpoint.unwrap().0));
+            self.set_checkpoint(B256::new(new_checkpoint.unwrap().0));
         }
     }
+
+    /// Records a new checkpoint, which must be the root of the finalized header.
+    ///
+    /// # Arguments
+    /// * `checkpoint` - Checkpoint returned by applying an update
+    fn set_checkpoint(&mut self, checkpoint: B256) {
+        let finalized_root = self.store.finalized_header.beacon.tree_hash_root();
+        assert_eq!(
+            checkpoint,
+            B256::new(finalized_root.0),
+            "checkpoint is not the finalized header"
+        );
+        self.last_checkpoint = Some(checkpoint);
+    }
 }
 
 /// Reads light client input data from the guest environment.
@@ -275,7 +322,7 @@ pub fn read_l1_chain_builder_input() -> (
 
 sol! {
     struct Journal {
-        /// The proof data bytes
+        /// The ABI encoded `Amounts` returned by `getProofData`
         bytes proof_data;
         /// The user's address
         address account;
@@ -283,14 +330,37 @@ sol! {
         address asset;
         /// trusted beacon root
         bytes32 checkpoint;
-        /// slot of the last update
+        /// slot of the last update, the slot of the verified beacon root
         uint64 slot_last_update;
-        /// new checkpoint
+        /// new checkpoint, a finalized beacon root at or before `slot_last_update`
         bytes32 new_checkpoint;
     }
 }
 
-/// Validates a proof data query using light client proofs.
+/// Decodes the journal committed by the combined guest.
+///
+/// The combined guest commits `abi.encode(bytes[] proofData, Journal lightClient)`: the
+/// packed entries of the sequencer path followed by the light client journal.
+///
+/// # Arguments
+/// * `journal` - The raw journal bytes
+///
+/// # Returns
+/// * `Result<(Vec<ProofDataJournalEntry>, Journal)>` - The decoded entries and light client journal
+pub fn decode_combined_journal(journal: &[u8]) -> Result<(Vec<ProofDataJournalEntry>, Journal)> {
+    let (entries, light_client) = <(Vec<Bytes>, Journal)>::abi_decode_params(journal, true)
+        .map_err(|e| eyre::eyre!("combined journal decode failed: {}", e))?;
+    let entries = entries
+        .iter()
+        .map(|entry| ProofDataJournalEntry::decode_packed(entry))
+        .collect::<Result<_>>()?;
+    Ok((entries, light_client))
+}
+
+/// Validates a proof data query using light client proofs and commits the journal.
+///
+/// Reads the light client inputs from the guest environment, runs
+/// `validate_and_build_journal` and commits the ABI encoded journal.
 ///
 /// # Arguments
 /// * `chain_id` - The chain ID to validate against
@@ -300,6 +370,48 @@ sol! {
 /// * `_sequencer_commitment` - Optional sequencer commitment
 /// * `_op_env_input` - Optional optimistic environment input
 /// * `linking_blocks` - Chain of blocks for verification
+pub fn validate_get_proof_data_call(
+    chain_id: u64,
+    account: Address,
+    asset: Address,
+    env_input: EthEvmInput,
+    _sequencer_commitment: Option<SequencerCommitment>,
+    _op_env_input: Option<EthEvmInput>,
+    linking_blocks: Vec<RlpHeader<ConsensusHeader>>,
+) {
+    let (bootstrap, checkpoint, updates, finality_update, beacon_input) =
+        read_l1_chain_builder_input();
+
+    let journal = validate_and_build_journal(
+        chain_id,
+        account,
+        asset,
+        env_input,
+        linking_blocks,
+        bootstrap,
+        checkpoint,
+        updates,
+        finality_update,
+        beacon_input,
+    );
+    env::commit_slice(&journal.abi_encode());
+}
+
+/// Validates a proof data query using light client proofs and builds its journal.
+///
+/// Performs no zkVM I/O, so it can be used host-side to re-validate inputs before proving.
+///
+/// # Arguments
+/// * `chain_id` - The chain ID to validate against
+/// * `account` - Account address to query
+/// * `asset` - Contract address to query
+/// * `env_input` - Ethereum environment input
+/// * `linking_blocks` - Chain of blocks for verification
+/// * `bootstrap` - Initial bootstrap data
+/// * `checkpoint` - Trust checkpoint
+/// * `updates` - Sequence of light client updates
+/// * `finality_update` - Latest optimistic update
+/// * `beacon_input` - Ethereum environment input committed to a beacon block root
 ///
 /// # Details
 ///
@@ -309,16 +421,23 @@ sol! {
 /// 3. Verifies beacon chain commitments
 /// 4. Executes and validates the proof data query
 ///
-/// Commits the results including proof data and checkpoints to the guest environment.
-pub fn validate_get_proof_data_call(
+/// # Returns
+/// * `Journal` - The proof data and checkpoints to commit
+///
+/// # Panics
+/// Panics if any of the validations fail
+pub fn validate_and_build_journal(
     chain_id: u64,
     account: Address,
     asset: Address,
     env_input: EthEvmInput,
-    _sequencer_commitment: Option<SequencerCommitment>,
-    _op_env_input: Option<EthEvmInput>,
     linking_blocks: Vec<RlpHeader<ConsensusHeader>>,
-) {
+    bootstrap: Bootstrap,
+    checkpoint: OldB256,
+    updates: Vec<Update>,
+    finality_update: OptimisticUpdate,
+    beacon_input: EthEvmInput,
+) -> Journal {
     let env = env_input.into_env();
 
     let contract = Contract::new(asset, &env);
@@ -327,52 +446,153 @@ pub fn validate_get_proof_data_call(
         account: account,
         dstChainId: chain_id as u32,
     };
-    let proof_data = contract.call_builder(&call).call()._0;
+    let amounts = contract.call_builder(&call).call()._0;
+    let proof_data = amounts.abi_encode().into();
 
     let last_block = if linking_blocks.is_empty() {
         env.header().inner().clone()
     } else {
         linking_blocks[linking_blocks.len() - 1].clone()
     };
-
-    let (bootstrap, checkpoint, updates, finality_update, beacon_input) =
-        read_l1_chain_builder_input();
+    // Hash every linking block once, the env header hash is already sealed by steel
+    let linking_block_hashes = hash_linking_blocks(&linking_blocks);
+    let last_block_hash = linking_block_hashes
+        .last()
+        .copied()
+        .unwrap_or(env.header().seal());
 
     let slot_last_update = finality_update.attested_header.beacon.slot;
 
     let (current_beacon_hash, new_checkpoint) =
         validate_ethereum_env_via_sync_committee(bootstrap, checkpoint, updates, finality_update);
 
-    validate_chain_length(
+    validate_chain_length_with_hashes(
         chain_id,
         env.header().seal(),
-        linking_blocks,
-        last_block.hash_slow(),
+        &linking_blocks,
+        &linking_block_hashes,
+        last_block_hash,
     );
+    validate_chain_numbers(env.header().inner().inner().number, &linking_blocks);
 
     let env = beacon_input.into_env();
-    let exec_commit = env.header().seal();
     let beacon_commit = env.commitment().digest;
 
     assert_eq!(
         beacon_commit, current_beacon_hash,
         "beacon commit doesnt correspond to current beacon hash"
     );
-    assert_eq!(
-        exec_commit,
-        last_block.hash_slow(),
-        "exec commit doesnt correspond to last block hash"
+    validate_beacon_execution_block(
+        env.header().seal(),
+        env.header().inner().inner().number,
+        &last_block,
+        last_block_hash,
     );
 
-    let journal = Journal {
+    Journal {
         proof_data,
         account,
         asset,
         checkpoint: B256::new(checkpoint.0),
         slot_last_update,
         new_checkpoint,
-    };
-    env::commit_slice(&journal.abi_encode());
+    }
+}
+
+/// Validates that a Linea block was posted to an Ethereum block verified by the light client.
+///
+/// Linea's slow lane otherwise takes the Ethereum block from OpStack's L1Block predeploy,
+/// which needs an OpStack sequencer commitment next to the Linea one. Here the Ethereum block
+/// is the execution block of the beacon block the light client synced to, so the L1
+/// inclusion only depends on Linea and Ethereum.
+///
+/// Not wired into a guest or host input builder yet. The caller must bind `linea_header` to
+/// the Linea env it queries, e.g. through `validate_chain_length`.
+///
+/// # Arguments
+/// * `chain_id` - The Linea chain ID; mainnet only, as the light client follows mainnet
+/// * `linea_header` - The Linea block to validate, checked against the Linea sequencer and
+///   the last Linea block posted to L1
+/// * `bootstrap` - Initial bootstrap data
+/// * `checkpoint` - Trust checkpoint
+/// * `updates` - Sequence of light client updates
+/// * `finality_update` - Latest optimistic update
+/// * `beacon_input` - Ethereum environment committed to a beacon block root, read for the
+///   last Linea block posted to L1
+///
+/// # Returns
+/// * `(B256, u64, B256)` - The trusted checkpoint, the slot of the last update and the new
+///   checkpoint, to commit next to the proof data so the verifier can check the checkpoint
+///
+/// # Panics
+/// Panics if:
+/// * Chain ID is not Linea mainnet
+/// * The Linea block is not signed by the Linea sequencer, see `validate_linea_env`
+/// * The light client doesn't sync, see `validate_ethereum_env_via_sync_committee`
+/// * The beacon input isn't committed to the verified beacon root
+/// * The Linea block is higher than the last one posted to L1
+pub fn validate_linea_env_via_light_client(
+    chain_id: u64,
+    linea_header: &RlpHeader<ConsensusHeader>,
+    bootstrap: Bootstrap,
+    checkpoint: OldB256,
+    updates: Vec<Update>,
+    finality_update: OptimisticUpdate,
+    beacon_input: EthEvmInput,
+) -> (B256, u64, B256) {
+    assert_eq!(
+        chain_id, LINEA_CHAIN_ID,
+        "light client L1 inclusion is only available on Linea mainnet"
+    );
+    validate_linea_env(chain_id, linea_header);
+
+    let slot_last_update = finality_update.attested_header.beacon.slot;
+
+    let (current_beacon_hash, new_checkpoint) =
+        validate_ethereum_env_via_sync_committee(bootstrap, checkpoint, updates, finality_update);
+
+    let env_eth = beacon_input.into_env();
+    assert_eq!(
+        env_eth.commitment().digest,
+        current_beacon_hash,
+        "beacon commit doesnt correspond to current beacon hash"
+    );
+
+    validate_linea_block_posted_to_l1(chain_id, linea_header.inner().number, &env_eth);
+
+    (B256::new(checkpoint.0), slot_last_update, new_checkpoint)
+}
+
+/// Validates that the execution block committed by the beacon block is the last linked block.
+///
+/// The beacon input commits to the execution payload of the verified beacon block, so its
+/// header is the execution block the light client anchors. The proof data must be linked to
+/// exactly that block, otherwise it would be anchored to an execution block the verified
+/// beacon chain doesn't reference.
+///
+/// # Arguments
+/// * `exec_hash` - Hash of the execution block committed by the beacon block
+/// * `exec_number` - Number of the execution block committed by the beacon block
+/// * `last_block` - Last block of the linked chain starting at the proof data block
+/// * `last_block_hash` - Hash of `last_block`
+///
+/// # Panics
+/// Panics if the block number or the block hash differ from the last linked block
+pub fn validate_beacon_execution_block(
+    exec_hash: B256,
+    exec_number: u64,
+    last_block: &RlpHeader<ConsensusHeader>,
+    last_block_hash: B256,
+) {
+    assert_eq!(
+        exec_number,
+        last_block.inner().number,
+        "exec block number doesnt correspond to last block number"
+    );
+    assert_eq!(
+        exec_hash, last_block_hash,
+        "exec commit doesnt correspond to last block hash"
+    );
 }
 
 /// Validates Ethereum environment using sync committee proofs.
@@ -385,16 +605,22 @@ pub fn validate_get_proof_data_call(
 ///
 /// # Returns
 /// Tuple of (current beacon root, new checkpoint)
+///
+/// # Panics
+/// Panics if the light client doesn't sync to the optimistic update's attested header, see
+/// `validate_light_client_head`
 pub fn validate_ethereum_env_via_sync_committee(
     bootstrap: Bootstrap,
     checkpoint: OldB256,
     updates: Vec<Update>,
     optimistic_update: OptimisticUpdate,
 ) -> (B256, B256) {
+    let slot_last_update = optimistic_update.attested_header.beacon.slot;
     let mut l1_chain_builder = L1ChainBuilder::new();
     let verified_root = l1_chain_builder
         .build_beacon_chain(bootstrap, checkpoint, updates, optimistic_update)
         .unwrap();
+    validate_light_client_head(&l1_chain_builder.store, slot_last_update);
 
     let verified_root = B256::new(verified_root.0);
 
@@ -405,50 +631,137 @@ pub fn validate_ethereum_env_via_sync_committee(
     (verified_root, new_checkpoint)
 }
 
-/// Validates chain length and block linking.
+/// Validates that a synced store is consistent with the slot committed to the journal.
+///
+/// The journal commits `slot_last_update` next to the verified root and the new checkpoint.
+/// The verified root is the store's optimistic header, so that header must be at
+/// `slot_last_update`, and the checkpoint, the finalized header, can't be past it.
 ///
 /// # Arguments
-/// * `chain_id` - Chain ID to determine reorg protection depth
-/// * `historical_hash` - Starting block hash
-/// * `linking_blocks` - Chain of blocks to verify
-/// * `current_hash` - Expected final block hash
+/// * `store` - The synced light client store
+/// * `slot_last_update` - Attested slot of the optimistic update
 ///
 /// # Panics
-/// * If chain length is insufficient for reorg protection
-/// * If blocks are not properly linked
-/// * If final hash doesn't match expected hash
-pub fn validate_chain_length(
-    chain_id: u64,
-    historical_hash: B256,
-    linking_blocks: Vec<RlpHeader<ConsensusHeader>>,
-    current_hash: B256,
-) {
-    let reorg_protection_depth = match chain_id {
-        OPTIMISM_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM,
-        BASE_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE,
-        LINEA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA,
-        ETHEREUM_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM,
-        SCROLL_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL,
-        OPTIMISM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_OPTIMISM_SEPOLIA,
-        BASE_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_BASE_SEPOLIA,
-        LINEA_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_LINEA_SEPOLIA,
-        ETHEREUM_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_ETHEREUM_SEPOLIA,
-        SCROLL_SEPOLIA_CHAIN_ID => REORG_PROTECTION_DEPTH_SCROLL_SEPOLIA,
-        _ => panic!("invalid chain id"),
-    };
-    let chain_length = linking_blocks.len() as u64;
+/// Panics if:
+/// * The optimistic header is not at `slot_last_update`, e.g. because the optimistic update
+///   was not applied
+/// * The finalized header is past the optimistic header
+pub fn validate_light_client_head(store: &LightClientStore, slot_last_update: u64) {
+    assert_eq!(
+        store.optimistic_header.beacon.slot, slot_last_update,
+        "verified root is not at the last update slot"
+    );
     assert!(
-        chain_length >= reorg_protection_depth,
-        "chain length is less than reorg protection"
+        store.finalized_header.beacon.slot <= slot_last_update,
+        "checkpoint is past the last update slot"
     );
-    let mut previous_hash = historical_hash;
-    for header in linking_blocks {
-        let parent_hash = header.parent_hash;
-        assert_eq!(parent_hash, previous_hash, "blocks not hashlinked");
-        previous_hash = header.hash_slow();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{linked_headers, HeaderBuilder};
+    use alloy_primitives::{address, U256};
+
+    #[test]
+    fn test_from_store_keeps_checkpoint_and_mainnet_config() {
+        let checkpoint = B256::repeat_byte(1);
+        let resumed = L1ChainBuilder::from_store(LightClientStore::default(), Some(checkpoint));
+        let fresh = L1ChainBuilder::new();
+
+        assert_eq!(resumed.last_checkpoint, Some(checkpoint));
+        assert_eq!(fresh.last_checkpoint, None);
+        assert_eq!(resumed.genesis_root, fresh.genesis_root);
+        assert_eq!(resumed.genesis_time, fresh.genesis_time);
+        assert_eq!(resumed.forks.deneb.epoch, fresh.forks.deneb.epoch);
+    }
+
+    fn store_at(finalized_slot: u64, optimistic_slot: u64) -> LightClientStore {
+        let mut store = LightClientStore::default();
+        store.finalized_header.beacon.slot = finalized_slot;
+        store.optimistic_header.beacon.slot = optimistic_slot;
+        store
+    }
+
+    #[test]
+    fn test_validate_light_client_head() {
+        validate_light_client_head(&store_at(64, 100), 100);
+        validate_light_client_head(&store_at(100, 100), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "verified root is not at the last update slot")]
+    fn test_validate_light_client_head_stale_optimistic_header() {
+        validate_light_client_head(&store_at(64, 90), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint is past the last update slot")]
+    fn test_validate_light_client_head_checkpoint_ahead() {
+        validate_light_client_head(&store_at(128, 100), 100);
+    }
+
+    #[test]
+    fn test_validate_beacon_execution_block() {
+        let last_block = HeaderBuilder::new().number(10).build();
+        let last_block_hash = last_block.hash_slow();
+        validate_beacon_execution_block(last_block_hash, 10, &last_block, last_block_hash);
+    }
+
+    #[test]
+    #[should_panic(expected = "exec block number doesnt correspond to last block number")]
+    fn test_validate_beacon_execution_block_other_number() {
+        let last_block = HeaderBuilder::new().number(10).build();
+        let last_block_hash = last_block.hash_slow();
+        validate_beacon_execution_block(last_block_hash, 11, &last_block, last_block_hash);
+    }
+
+    #[test]
+    #[should_panic(expected = "exec commit doesnt correspond to last block hash")]
+    fn test_validate_beacon_execution_block_other_hash() {
+        let chain = linked_headers(B256::ZERO, 2);
+        let last_block = &chain[1];
+        let sibling = HeaderBuilder::new()
+            .parent_hash(chain[0].hash_slow())
+            .number(last_block.inner().number)
+            .extra_data(Bytes::from_static(b"sibling"))
+            .build();
+        validate_beacon_execution_block(
+            sibling.hash_slow(),
+            sibling.inner().number,
+            last_block,
+            last_block.hash_slow(),
+        );
+    }
+
+    #[test]
+    fn test_decode_combined_journal() {
+        let entry = ProofDataJournalEntry {
+            user: address!("0000000000000000000000000000000000000001"),
+            market: address!("0000000000000000000000000000000000000002"),
+            amount_in: U256::from(3),
+            amount_out: U256::from(4),
+            chain_id: BASE_CHAIN_ID,
+            target_chain_id: LINEA_CHAIN_ID,
+            l1_inclusion: false,
+            reorg_protection_depth: 2,
+        };
+        let light_client = Journal {
+            proof_data: Bytes::from(vec![5u8; 64]),
+            account: entry.user,
+            asset: entry.market,
+            checkpoint: B256::repeat_byte(6),
+            slot_last_update: 7,
+            new_checkpoint: B256::repeat_byte(8),
+        };
+
+        let journal = (vec![entry.encode_packed()], light_client.clone()).abi_encode_params();
+        let (entries, decoded) = decode_combined_journal(&journal).unwrap();
+
+        assert_eq!(entries, vec![entry]);
+        assert_eq!(decoded.proof_data, light_client.proof_data);
+        assert_eq!(decoded.checkpoint, light_client.checkpoint);
+        assert_eq!(decoded.slot_last_update, light_client.slot_last_update);
+        assert_eq!(decoded.new_checkpoint, light_client.new_checkpoint);
     }
-    assert_eq!(
-        previous_hash, current_hash,
-        "last hash doesnt correspond to current l1 hash"
-    );
 }
@@ -13,6 +13,17 @@
 use risc0_build::{embed_methods_with_options, DockerOptionsBuilder, GuestOptionsBuilder};
 use std::{collections::HashMap, env, fs, path::PathBuf};
 
+// Guest constant prefix and the ELF file name referenced from malda_rs
+const GUESTS: [(&str, &str); 4] = [
+    ("GET_PROOF_DATA", "get-proof-data"),
+    (
+        "GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT",
+        "get_proof_data_ethereum_light_client",
+    ),
+    ("GET_PROOF_DATA_COMBINED", "get_proof_data_combined"),
+    ("CONTRACT_CALL", "contract_call"),
+];
+
 fn main() {
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
     let root_dir = manifest_dir.parent().unwrap().to_path_buf();
@@ -38,82 +49,85 @@ fn main() {
     // Copy and rename specific files
     let methods_path = out_dir.join("methods.rs");
     let elfs_ids_path = malda_rs_src.join("elfs_ids.rs");
-    fs::copy(&methods_path, &elfs_ids_path).unwrap();
 
-    // Read elfs_ids.rs to get the original ELF paths
-    let elfs_ids_content = fs::read_to_string(&elfs_ids_path).unwrap();
-
-    // Copy the ELF files to malda_rs/bin
-    if let Some(path_line) = elfs_ids_content
-        .lines()
-        .find(|line| line.contains("GET_PROOF_DATA_PATH"))
-    {
-        if let Some(path) = path_line.split('"').nth(1) {
-            let source_path = PathBuf::from(path);
-            let filename = source_path.file_name().unwrap();
-            let dest_path = malda_rs_bin.join(filename);
-            fs::copy(&source_path, &dest_path).unwrap();
-            println!(
-                "Copied ELF file from {} to {}",
-                source_path.display(),
-                dest_path.display()
-            );
+    // The committed image IDs are the ones the verifiers are deployed with, so refuse to
+    // overwrite them with different ones unless asked to.
+    println!("cargo:rerun-if-env-changed=REGENERATE_ELFS_IDS");
+    if env::var("REGENERATE_ELFS_IDS").is_err() {
+        let committed = fs::read_to_string(&elfs_ids_path).unwrap_or_default();
+        let built = fs::read_to_string(&methods_path).unwrap();
+        for (prefix, _) in GUESTS {
+            let committed_id = image_id_line(&committed, prefix);
+            let built_id = image_id_line(&built, prefix);
+            if committed_id != built_id {
+                panic!(
+                    "{} image ID changed: committed {:?}, built {:?}. Rebuild with \
+                     REGENERATE_ELFS_IDS=1, commit elfs_ids.rs and malda_rs/bin and redeploy \
+                     the verifier",
+                    prefix, committed_id, built_id
+                );
+            }
         }
     }
 
-    if let Some(path_line) = elfs_ids_content
-        .lines()
-        .find(|line| line.contains("GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_PATH"))
-    {
-        if let Some(path) = path_line.split('"').nth(1) {
-            let source_path = PathBuf::from(path);
-            let filename = source_path.file_name().unwrap();
-            let dest_path = malda_rs_bin.join(filename);
-            fs::copy(&source_path, &dest_path).unwrap();
-            println!(
-                "Copied ELF file from {} to {}",
-                source_path.display(),
-                dest_path.display()
-            );
-        }
-    }
+    fs::copy(&methods_path, &elfs_ids_path).unwrap();
 
-    // Now update the paths in elfs_ids.rs to use relative paths
-    let mut elfs_ids_content = elfs_ids_content.replace(
-        "pub const GET_PROOF_DATA_ELF: &[u8] = &[];",
-        "pub const GET_PROOF_DATA_ELF: &[u8] = include_bytes!(\"../bin/get-proof-data\");",
-    );
+    // Read elfs_ids.rs to get the original ELF paths
+    let mut elfs_ids_content = fs::read_to_string(&elfs_ids_path).unwrap();
 
-    elfs_ids_content = elfs_ids_content.replace(
-        "pub const GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ELF: &[u8] = &[];",
-        "pub const GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ELF: &[u8] = include_bytes!(\"../bin/get_proof_data_ethereum_light_client\");"
-    );
+    for (prefix, elf_name) in GUESTS {
+        let path_const = format!("{}_PATH:", prefix);
 
-    // Extract just the filenames for the paths
-    if let Some(path_line) = elfs_ids_content
-        .lines()
-        .find(|line| line.contains("GET_PROOF_DATA_PATH"))
-    {
-        if let Some(path) = path_line.split('"').nth(1) {
-            let path_buf = PathBuf::from(path);
-            let file_name = path_buf.file_name().unwrap();
-            let filename = file_name.to_str().unwrap();
-            elfs_ids_content = elfs_ids_content.replace(path, &format!("../bin/{}", filename));
+        // Copy the ELF file to malda_rs/bin
+        if let Some(path_line) = elfs_ids_content
+            .lines()
+            .find(|line| line.contains(&path_const))
+        {
+            if let Some(path) = path_line.split('"').nth(1) {
+                let source_path = PathBuf::from(path);
+                let filename = source_path.file_name().unwrap();
+                let dest_path = malda_rs_bin.join(filename);
+                fs::copy(&source_path, &dest_path).unwrap();
+                println!(
+                    "Copied ELF file from {} to {}",
+                    source_path.display(),
+                    dest_path.display()
+                );
+            }
         }
-    }
 
-    if let Some(path_line) = elfs_ids_content
-        .lines()
-        .find(|line| line.contains("GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_PATH"))
-    {
-        if let Some(path) = path_line.split('"').nth(1) {
-            let path_buf = PathBuf::from(path);
-            let file_name = path_buf.file_name().unwrap();
-            let filename = file_name.to_str().unwrap();
-            elfs_ids_content = elfs_ids_content.replace(path, &format!("../bin/{}", filename));
+        // Now update the paths in elfs_ids.rs to use relative paths
+        elfs_ids_content = elfs_ids_content.replace(
+            &format!("pub const {}_ELF: &[u8] = &[];", prefix),
+            &format!(
+                "pub const {}_ELF: &[u8] = include_bytes!(\"../bin/{}\");",
+                prefix, elf_name
+            ),
+        );
+
+        // Extract just the filenames for the paths
+        if let Some(path_line) = elfs_ids_content
+            .lines()
+            .find(|line| line.contains(&path_const))
+        {
+            if let Some(path) = path_line.split('"').nth(1) {
+                let path_buf = PathBuf::from(path);
+                let file_name = path_buf.file_name().unwrap();
+                let filename = file_name.to_str().unwrap();
+                elfs_ids_content = elfs_ids_content.replace(path, &format!("../bin/{}", filename));
+            }
         }
     }
 
     // Write the updated content back to elfs_ids.rs
     fs::write(&elfs_ids_path, elfs_ids_content).unwrap();
 }
+
+/// Returns the `<prefix>_ID` line of a generated methods source, if present.
+fn image_id_line<'a>(content: &'a str, prefix: &str) -> Option<&'a str> {
+    let id_const = format!("pub const {}_ID:", prefix);
+    content
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with(&id_const))
+}
@@ -0,0 +1,43 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+
+use malda_utils::{validators::validate_contract_call, types::{ContractCall, ContractCallJournalEntry, SequencerCommitment}};
+use risc0_steel::{ethereum::EthEvmInput, serde::RlpHeader};
+use risc0_op_steel::optimism::OpEvmInput;
+use risc0_zkvm::guest::env;
+use alloy_consensus::Header;
+use alloy_sol_types::SolValue;
+use malda_utils::constants::is_supported_chain;
+
+fn main() {
+    // Same input as get-proof-data, with the calls in place of users, markets and targets
+    let mut output: Vec<ContractCallJournalEntry> = Vec::new();
+    let length: u64 = env::read();
+    for _i in 0..length {
+        let env_input: Option<EthEvmInput> = env::read();
+        let chain_id: u64 = env::read();
+        // Fail before reading the rest of the entry
+        assert!(is_supported_chain(chain_id), "Chain ID {} is not Linea, Base or Ethereum", chain_id);
+        let calls: Vec<ContractCall> = env::read();
+        let sequencer_commitment: Option<SequencerCommitment> = env::read();
+        let env_op_input: Option<EthEvmInput> = env::read();
+        let linking_blocks: Vec<RlpHeader<Header>> = env::read();
+        let env_eth_input: Option<EthEvmInput> = env::read();
+        let op_evm_input: Option<OpEvmInput> = env::read();
+        let sequencer_commitment_opstack_2: Option<SequencerCommitment> = env::read();
+        let env_op_input_2: Option<EthEvmInput> = env::read();
+
+        validate_contract_call(chain_id, calls, env_input, sequencer_commitment, env_op_input, &linking_blocks, &mut output, &env_eth_input, op_evm_input, sequencer_commitment_opstack_2, env_op_input_2);
+    }
+    env::commit_slice(&output.abi_encode());
+}
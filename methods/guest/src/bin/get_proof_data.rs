@@ -18,7 +18,7 @@ use risc0_op_steel::optimism::OpEvmInput;
 use risc0_zkvm::guest::env;
 use alloy_consensus::Header;
 use alloy_sol_types::SolValue;
-use malda_utils::constants::{LINEA_CHAIN_ID, BASE_CHAIN_ID, ETHEREUM_CHAIN_ID};
+use malda_utils::constants::is_supported_chain;
 
 fn main() {
     let mut output: Vec<Bytes> = Vec::new();
@@ -27,6 +27,8 @@ fn main() {
         // Read the input data for this application.
         let env_input: Option<EthEvmInput> = env::read();
         let chain_id: u64 = env::read();
+        // Fail before reading the rest of the entry
+        assert!(is_supported_chain(chain_id), "Chain ID {} is not Linea, Base or Ethereum", chain_id);
         let account: Vec<Address> = env::read();
         let asset: Vec<Address> = env::read();
         let target_chain_ids: Vec<u64> = env::read();
@@ -38,10 +40,6 @@ fn main() {
         let sequencer_commitment_opstack_2: Option<SequencerCommitment> = env::read();
         let env_op_input_2: Option<EthEvmInput> = env::read();
 
-        if chain_id != LINEA_CHAIN_ID && chain_id != BASE_CHAIN_ID && chain_id != ETHEREUM_CHAIN_ID {
-            panic!("Chain ID is not Linea, Base or Ethereum");
-        }
-        
         validate_get_proof_data_call(chain_id, account, asset, target_chain_ids, env_input, sequencer_commitment, env_op_input, &linking_blocks, &mut output, &env_eth_input, op_evm_input, sequencer_commitment_opstack_2, env_op_input_2);
     }
     env::commit_slice(&output.abi_encode());
@@ -0,0 +1,60 @@
+// Copyright (c) 2025 Merge Layers Inc.
+//
+// This source code is licensed under the Business Source License 1.1
+// (the "License"); you may not use this file except in compliance with the
+// License. You may obtain a copy of the License at
+//
+//     https://github.com/malda-protocol/malda-zk-coprocessor/blob/main/LICENSE-BSL
+//
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+//
+
+use malda_utils::{validators::validate_get_proof_data_call, validators_ethereum_light_client::{read_l1_chain_builder_input, validate_and_build_journal}, types::SequencerCommitment};
+use alloy_primitives::{Address, Bytes};
+use risc0_steel::{ethereum::EthEvmInput, serde::RlpHeader};
+use risc0_op_steel::optimism::OpEvmInput;
+use risc0_zkvm::guest::env;
+use alloy_consensus::Header;
+use alloy_sol_types::SolValue;
+use malda_utils::constants::is_supported_chain;
+
+fn main() {
+    // Sequencer commitment path, same input as get-proof-data
+    let mut output: Vec<Bytes> = Vec::new();
+    let length: u64 = env::read();
+    for _i in 0..length {
+        let env_input: Option<EthEvmInput> = env::read();
+        let chain_id: u64 = env::read();
+        // Fail before reading the rest of the entry
+        assert!(is_supported_chain(chain_id), "Chain ID {} is not Linea, Base or Ethereum", chain_id);
+        let account: Vec<Address> = env::read();
+        let asset: Vec<Address> = env::read();
+        let target_chain_ids: Vec<u64> = env::read();
+        let sequencer_commitment: Option<SequencerCommitment> = env::read();
+        let env_op_input: Option<EthEvmInput> = env::read();
+        let linking_blocks: Vec<RlpHeader<Header>> = env::read();
+        let env_eth_input: Option<EthEvmInput> = env::read();
+        let op_evm_input: Option<OpEvmInput> = env::read();
+        let sequencer_commitment_opstack_2: Option<SequencerCommitment> = env::read();
+        let env_op_input_2: Option<EthEvmInput> = env::read();
+
+        validate_get_proof_data_call(chain_id, account, asset, target_chain_ids, env_input, sequencer_commitment, env_op_input, &linking_blocks, &mut output, &env_eth_input, op_evm_input, sequencer_commitment_opstack_2, env_op_input_2);
+    }
+
+    // Light client path, same input as get_proof_data_ethereum_light_client
+    let env_input: EthEvmInput = env::read();
+    let chain_id: u64 = env::read();
+    let account: Address = env::read();
+    let asset: Address = env::read();
+    let _sequencer_commitment: Option<SequencerCommitment> = env::read();
+    let _env_op_input: Option<EthEvmInput> = env::read();
+    let linking_blocks: Vec<RlpHeader<Header>> = env::read();
+    let (bootstrap, checkpoint, updates, finality_update, beacon_input) = read_l1_chain_builder_input();
+
+    let light_client_journal = validate_and_build_journal(chain_id, account, asset, env_input, linking_blocks, bootstrap, checkpoint, updates, finality_update, beacon_input);
+
+    // abi.encode(bytes[] proofData, Journal lightClient), see decode_combined_journal
+    env::commit_slice(&(output, light_client_journal).abi_encode_params());
+}
@@ -16,16 +16,31 @@ include!(concat!(env!("OUT_DIR"), "/methods.rs"));
 #[cfg(test)]
 mod tests {
 
+    use alloy::sol_types::SolValue;
     use alloy_primitives::{address, Address, B256};
     use hex;
+    use malda_rs::elfs_ids::{
+        GET_PROOF_DATA_ELF, GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ID, GET_PROOF_DATA_ID,
+    };
     use malda_rs::{
         constants::*,
+        seal::{ensure_journal_lane, ensure_seal_selector, ensure_submittable, seal_selector},
+        types::{Network, ProofLane},
+        validators::batch_call_get_proof_data,
         viewcalls::{
-            get_proof_data_exec, get_proof_data_prove,
-            get_proof_data_prove_sdk,
+            estimate_proof_cycles, exec_env, exec_input_with_limits, get_linea_l1_posted_block,
+            get_proof_data_call_input_at, get_proof_data_exec, get_proof_data_prove,
+            get_proof_data_prove_sdk, get_proof_data_zkvm_input_with_options,
+            verify_elf_id_consistency, ExecutorLimits, MaldaError, ZkvmInputOptions,
         },
         viewcalls_ethereum_light_client::get_proof_data_exec as get_proof_data_exec_ethereum_light_client,
     };
+    use risc0_ethereum_contracts::{
+        encode_seal,
+        receipt::{decode_seal, Receipt as DecodedReceipt},
+        selector::Selector,
+    };
+    use risc0_zkvm::{default_prover, sha::Digest, ExecutorEnv, ProverOpts};
 
     pub const WETH_MARKET_SEPOLIA: Address = address!("B84644c24B4D0823A0770ED698f7C20B88Bcf824");
     pub const WETH_MARKET: Address = address!("C7Bc6bD45Eb84D594f51cED3c5497E6812C7732f");
@@ -52,6 +67,44 @@ mod tests {
 
     }
 
+    #[tokio::test]
+    async fn should_estimate_proof_cycles_on_linea() {
+        let user_linea = address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3");
+
+        let stats = estimate_proof_cycles(
+            vec![vec![user_linea]],
+            vec![vec![WETH_MARKET]],
+            vec![vec![OPTIMISM_CHAIN_ID]],
+            vec![LINEA_CHAIN_ID],
+            false,
+        )
+        .await
+        .unwrap();
+
+        println!("Estimated cycles: {:?}", stats);
+        assert!(stats.segments > 0);
+        assert!(stats.total_cycles >= stats.user_cycles);
+    }
+
+    #[test]
+    fn should_report_session_limit_exceeded() {
+        // The executor checks the limit before every instruction, so one cycle is exceeded
+        // before the guest reads its input.
+        let limits = ExecutorLimits {
+            session_limit: Some(1),
+            segment_limit_po2: None,
+        };
+
+        let err = exec_input_with_limits(&[], limits).unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::SessionLimitExceeded {
+                session_limit: Some(1),
+            })
+        );
+    }
+
     #[tokio::test]
     async fn should_pass_prove_sepolia_get_proof_data_on_linea() {
         let user_linea = address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3");
@@ -62,7 +115,7 @@ mod tests {
         let session_info = get_proof_data_exec(
             vec![vec![user_linea]],
             vec![vec![asset]],
-            vec![vec![OPTIMISM_CHAIN_ID]],
+            vec![vec![OPTIMISM_SEPOLIA_CHAIN_ID]],
             vec![chain_id],
             false,
         )
@@ -86,7 +139,7 @@ mod tests {
         let session_info = get_proof_data_exec(
             vec![vec![user_linea]],
             vec![vec![asset]],
-            vec![vec![OPTIMISM_CHAIN_ID]],
+            vec![vec![OPTIMISM_SEPOLIA_CHAIN_ID]],
             vec![chain_id],
             true,
         )
@@ -101,6 +154,53 @@ mod tests {
     }
 
 
+    #[tokio::test]
+    async fn should_commit_same_amounts_on_fast_and_slow_lane() {
+        // Both lanes query the market through the same multicall and only differ in how the
+        // block is anchored. The slow lane queries the last Linea block posted to L1 and the
+        // fast lane the head, so both are pinned to the posted block and run through the
+        // guest's journal code.
+        let user_linea = address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3");
+        let block = get_linea_l1_posted_block(LINEA_SEPOLIA_CHAIN_ID, rpc_url_ethereum_sepolia())
+            .await
+            .unwrap();
+
+        let mut amounts = Vec::new();
+        for lane in [ProofLane::Fast, ProofLane::Slow] {
+            let (input, _) = get_proof_data_call_input_at(
+                LINEA_SEPOLIA_CHAIN_ID,
+                rpc_url_linea_sepolia(),
+                block,
+                vec![user_linea],
+                vec![WETH_MARKET_SEPOLIA],
+                vec![vec![OPTIMISM_SEPOLIA_CHAIN_ID]],
+                lane.l1_inclusion(),
+            )
+            .await
+            .unwrap();
+            let env = input.unwrap().into_env();
+            assert_eq!(env.header().inner().inner().number, block);
+
+            let mut output = Vec::new();
+            batch_call_get_proof_data(
+                LINEA_SEPOLIA_CHAIN_ID,
+                vec![user_linea],
+                vec![WETH_MARKET_SEPOLIA],
+                vec![OPTIMISM_SEPOLIA_CHAIN_ID],
+                env,
+                lane.l1_inclusion(),
+                0,
+                &mut output,
+            );
+
+            let entries = ensure_journal_lane(&output.abi_encode(), lane).unwrap();
+            assert_eq!(entries.len(), 1);
+            amounts.push((entries[0].amount_in, entries[0].amount_out));
+        }
+
+        assert_eq!(amounts[0], amounts[1], "fast and slow lane amounts diverge");
+    }
+
     #[tokio::test]
     async fn should_pass_prove_get_proof_data_on_optimism_sepolia_sdk() {
         let user_optimism = address!("e50fA9b3c56FfB159cB0FCA61F5c9D750e8128c8");
@@ -110,7 +210,7 @@ mod tests {
         let _session_info = get_proof_data_prove_sdk(
             vec![vec![user_optimism]],
             vec![vec![asset]],
-            vec![vec![LINEA_CHAIN_ID]],
+            vec![vec![LINEA_SEPOLIA_CHAIN_ID]],
             vec![chain_id],
             false,
         )
@@ -128,7 +228,7 @@ mod tests {
         let session_info = get_proof_data_exec(
             vec![vec![user_optimism]],
             vec![vec![asset]],
-            vec![vec![LINEA_CHAIN_ID]],
+            vec![vec![LINEA_SEPOLIA_CHAIN_ID]],
             vec![chain_id],
             true,
         )
@@ -140,6 +240,51 @@ mod tests {
     }
 
 
+    #[tokio::test]
+    async fn should_reject_mixed_mainnet_and_sepolia_batch() {
+        let user = address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3");
+
+        let err = get_proof_data_exec(
+            vec![vec![user], vec![user]],
+            vec![vec![WETH_MARKET], vec![WETH_MARKET_SEPOLIA]],
+            vec![vec![OPTIMISM_CHAIN_ID], vec![LINEA_SEPOLIA_CHAIN_ID]],
+            vec![LINEA_CHAIN_ID, OPTIMISM_SEPOLIA_CHAIN_ID],
+            false,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::MixedNetworks {
+                chain_ids: vec![LINEA_CHAIN_ID, OPTIMISM_SEPOLIA_CHAIN_ID],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_reject_target_chain_on_other_network() {
+        let user = address!("2693946791da99dA78Ac441abA6D5Ce2Bccd96D3");
+
+        let err = get_proof_data_exec(
+            vec![vec![user]],
+            vec![vec![WETH_MARKET]],
+            vec![vec![LINEA_SEPOLIA_CHAIN_ID]],
+            vec![LINEA_CHAIN_ID],
+            false,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<MaldaError>(),
+            Some(&MaldaError::TargetNetworkMismatch {
+                network: Network::Mainnet,
+                target_chain_id: LINEA_SEPOLIA_CHAIN_ID,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn prove_get_proof_data_on_base() {
         let user_base = address!("6446021F4E396dA3df4235C62537431372195D38");
@@ -189,7 +334,7 @@ mod tests {
         let session_info = get_proof_data_exec(
             vec![vec![user_ethereum]],
             vec![vec![asset]],
-            vec![vec![LINEA_CHAIN_ID]],
+            vec![vec![LINEA_SEPOLIA_CHAIN_ID]],
             vec![chain_id],
             false,
         )
@@ -200,6 +345,114 @@ mod tests {
         println!("Cycles: {}", cycles);
     }
 
+    #[tokio::test]
+    async fn should_pass_prove_get_proof_data_on_ethereum_sepolia_via_op_and_base() {
+        let user_ethereum = address!("F04a5cC80B1E94C69B48f5ee68a08CD2F09A7c3E");
+        let asset = WETH_MARKET_SEPOLIA;
+        let chain_id = ETHEREUM_SEPOLIA_CHAIN_ID;
+
+        let input = get_proof_data_zkvm_input_with_options(
+            vec![user_ethereum],
+            vec![asset],
+            vec![LINEA_SEPOLIA_CHAIN_ID],
+            chain_id,
+            false,
+            ZkvmInputOptions {
+                dual_opstack_l1_attestation: true,
+                ..ZkvmInputOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let env = ExecutorEnv::builder()
+            .write(&1u64)
+            .unwrap()
+            .write_slice(&input)
+            .build()
+            .unwrap();
+        let session_info = exec_env(env).unwrap();
+
+        let cycles = session_info.segments.iter().map(|s| s.cycles).sum::<u32>();
+        println!("Cycles: {}", cycles);
+    }
+
+    /// Encodes a Groth16 seal the way the on-chain submission does and checks that the
+    /// verifier router would route and accept it. Set `RISC0_DEV_MODE=1` to use a fake
+    /// Groth16 receipt instead of running the local Groth16 prover.
+    #[tokio::test]
+    async fn should_verify_encoded_seal_against_verifier_selector() {
+        // An empty batch commits an empty `bytes[]` journal and needs no RPC access.
+        let env = ExecutorEnv::builder()
+            .write(&0u64)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let receipt = tokio::task::spawn_blocking(move || {
+            default_prover()
+                .prove_with_opts(env, GET_PROOF_DATA_ELF, &ProverOpts::groth16())
+                .unwrap()
+                .receipt
+        })
+        .await
+        .unwrap();
+
+        let seal = encode_seal(&receipt).unwrap();
+        let selector: [u8; 4] = seal[..4].try_into().unwrap();
+        assert!(
+            Selector::from_bytes(selector).is_some(),
+            "seal selector unknown to the verifier router"
+        );
+        ensure_submittable(&receipt.journal.bytes, &seal).unwrap();
+        assert_eq!(seal_selector(&seal), Some(selector));
+        ensure_seal_selector(&seal, selector).unwrap();
+        assert!(ensure_seal_selector(&seal, [0u8; 4]).is_err());
+
+        let decoded = decode_seal(
+            seal.into(),
+            GET_PROOF_DATA_ID,
+            receipt.journal.bytes.clone(),
+        )
+        .unwrap();
+        match decoded {
+            DecodedReceipt::Base(decoded) => decoded.verify(GET_PROOF_DATA_ID).unwrap(),
+            _ => panic!("seal decoded to a set inclusion receipt"),
+        }
+    }
+
+    #[test]
+    fn should_embed_elfs_matching_their_image_ids() {
+        verify_elf_id_consistency().unwrap();
+    }
+
+    #[test]
+    fn should_build_guests_with_embedded_image_ids() {
+        // Recomputed from the ELFs this build produced. `build.rs` refuses to overwrite the
+        // committed IDs with different ones unless `REGENERATE_ELFS_IDS` is set, so this
+        // checks that the ELFs malda_rs embeds are the ones that were built.
+        for (name, elf, embedded_id) in [
+            (
+                "get-proof-data",
+                crate::GET_PROOF_DATA_ELF,
+                GET_PROOF_DATA_ID,
+            ),
+            (
+                "get_proof_data_ethereum_light_client",
+                crate::GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ELF,
+                GET_PROOF_DATA_ETHEREUM_LIGHT_CLIENT_ID,
+            ),
+        ] {
+            let image_id = risc0_zkvm::compute_image_id(elf).unwrap();
+            assert_eq!(
+                image_id,
+                Digest::from(embedded_id),
+                "{} guest changed, regenerate elfs_ids.rs and redeploy its verifier",
+                name
+            );
+        }
+    }
+
     // #[tokio::test]
     // async fn prove_get_proof_data_on_ethereum_via_light_client() {
     //     let user_ethereum = address!("F04a5cC80B1E94C69B48f5ee68a08CD2F09A7c3E");
@@ -25,8 +25,10 @@ use once_cell::sync::Lazy;
 
 pub use self::DynamicContext as Context;
 use sha2_impl::Sha2CrateImpl;
+pub use zero_hashes::{PRECOMPUTED_ZERO_HASHES, ZERO_HASHES_MAX_INDEX};
 
 mod sha2_impl;
+mod zero_hashes;
 
 /// Length of a SHA256 hash in bytes.
 pub const HASH_LEN: usize = 32;
@@ -51,6 +53,24 @@ pub fn hash32_concat(h1: &[u8], h2: &[u8]) -> [u8; 32] {
     ctxt.finalize()
 }
 
+/// Compute the hash of any number of slices concatenated, without an intermediate buffer.
+pub fn hash_concat_many(parts: &[&[u8]]) -> [u8; HASH_LEN] {
+    let mut ctxt = DynamicContext::new();
+    for part in parts {
+        ctxt.update(part);
+    }
+    ctxt.finalize()
+}
+
+/// Compute the hash of a sequence of 32-byte nodes concatenated.
+pub fn hash_nodes(nodes: &[[u8; HASH_LEN]]) -> [u8; HASH_LEN] {
+    let mut ctxt = DynamicContext::new();
+    for node in nodes {
+        ctxt.update(node);
+    }
+    ctxt.finalize()
+}
+
 /// Context trait for abstracting over implementation contexts.
 pub trait Sha256Context {
     fn new() -> Self;
@@ -132,10 +152,6 @@ impl Sha256Context for DynamicContext {
     }
 }
 
-/// The max index that can be used with `ZERO_HASHES`.
-#[cfg(feature = "zero_hash_cache")]
-pub const ZERO_HASHES_MAX_INDEX: usize = 48;
-
 #[cfg(feature = "zero_hash_cache")]
 /// Cached zero hashes where `ZERO_HASHES[i]` is the hash of a Merkle tree with 2^i zero leaves.
 pub static ZERO_HASHES: Lazy<Vec<[u8; HASH_LEN]>> = Lazy::new(|| {
@@ -167,6 +183,47 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_hash_concat_many() {
+        let output = hash_concat_many(&[b"hello", b" ", b"world"]);
+        let expected_hex = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+        let expected: Vec<u8> = expected_hex.from_hex().unwrap();
+        assert_eq!(expected, output);
+
+        assert_eq!(hash_concat_many(&[]), hash_fixed(&[]));
+        assert_eq!(
+            hash_concat_many(&[&[1; 32], &[2; 32]]),
+            hash32_concat(&[1; 32], &[2; 32])
+        );
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_hash_nodes() {
+        let output = hash_nodes(&[[0; 32], [0; 32]]);
+        let expected_hex = "f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b";
+        let expected: Vec<u8> = expected_hex.from_hex().unwrap();
+        assert_eq!(expected, output);
+
+        let output = hash_nodes(&[[1; 32], [2; 32], [3; 32]]);
+        let expected_hex = "8a2e491356cfdb05a1d13785e0794d7cd163f91af79a146c976b1d2ac643b679";
+        let expected: Vec<u8> = expected_hex.from_hex().unwrap();
+        assert_eq!(expected, output);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_precomputed_zero_hashes() {
+        assert_eq!(PRECOMPUTED_ZERO_HASHES[0], [0; 32]);
+        for i in 0..ZERO_HASHES_MAX_INDEX {
+            assert_eq!(
+                PRECOMPUTED_ZERO_HASHES[i + 1],
+                hash32_concat(&PRECOMPUTED_ZERO_HASHES[i], &PRECOMPUTED_ZERO_HASHES[i])
+            );
+        }
+    }
+
     #[cfg(feature = "zero_hash_cache")]
     mod zero_hash {
         use super::*;
@@ -175,5 +232,13 @@ mod tests {
         fn zero_hash_zero() {
             assert_eq!(ZERO_HASHES[0], [0; 32]);
         }
+
+        #[test]
+        fn precomputed_zero_hashes_match_cache() {
+            assert_eq!(ZERO_HASHES.len(), PRECOMPUTED_ZERO_HASHES.len());
+            for (i, hash) in PRECOMPUTED_ZERO_HASHES.iter().enumerate() {
+                assert_eq!(ZERO_HASHES[i], *hash, "zero hash mismatch at index {}", i);
+            }
+        }
     }
 }